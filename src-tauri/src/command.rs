@@ -1,14 +1,34 @@
-use tauri::AppHandle;
+use chrono::Utc;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
 use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
 
 use crate::fcm::{
-    auth::{authenticate, AuthResult, OAuthToken},
+    account::{account_email, AccountList, AccountSummary},
+    auth::{
+        authenticate, logout_account, refresh_access_token, AuthResult, LogoutResult, OAuthToken,
+    },
     client::FcmClient,
-    config::FcmConfig,
+    config::{AuthMode, FcmConfig},
     history::{HistoryEntry, HistoryList},
+    id_token::AccountInfo,
+    lint::{lint_request, LintReport},
     message::{SendRequest, SendResult},
-    template::{Template, TemplateList},
+    preview::{preview_request, SendPreview},
+    profile::{Profile, ProfileList},
+    raw::{from_raw, to_raw, validate_raw_message, RawConversion, RawIssue},
+    service_account::{authenticate_service_account, ServiceAccountKey},
+    template::{Template, TemplateList, TemplateRenderError},
+    vault::{
+        load_machine_secret, TokenBackend, TokenStorageSettings, TokenStorageStatus, VaultKey,
+        VaultKeySource,
+    },
 };
+use crate::modules::{logger, types::OAuthProgressEvent};
+use crate::AppState;
 
 const CONFIG_STORE: &str = "config.json";
 const TOKEN_STORE: &str = "token.json";
@@ -19,31 +39,192 @@ const HISTORY_STORE: &str = "history.json";
 // 설정 관련 커맨드
 // ============================================================================
 
-#[tauri::command]
-#[specta::specta]
-pub async fn get_config(app: AppHandle) -> Result<FcmConfig, String> {
+/// 프로필 목록 로드 (단일 config만 있던 이전 버전 데이터는 기본 프로필로 마이그레이션)
+fn load_profiles(app: &AppHandle) -> Result<ProfileList, String> {
     let store = app
         .store(CONFIG_STORE)
         .map_err(|e| format!("스토어 열기 실패: {}", e))?;
 
-    let config: FcmConfig = store
+    if let Some(profiles) = store
+        .get("profiles")
+        .and_then(|v| serde_json::from_value::<ProfileList>(v).ok())
+        .filter(|p| !p.profiles.is_empty())
+    {
+        return Ok(profiles);
+    }
+
+    let legacy_config: FcmConfig = store
         .get("config")
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
+    let profiles = ProfileList::from_config(legacy_config);
+
+    // 기존 토큰을 기본 프로필의 토큰 슬롯으로 이동
+    let token_store = app
+        .store(TOKEN_STORE)
+        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+    if let Some(token) = token_store.get("token") {
+        token_store.set(
+            format!("{}{}", LEGACY_TOKEN_PREFIX, profiles.active_id),
+            token,
+        );
+        token_store.delete("token");
+        token_store
+            .save()
+            .map_err(|e| format!("저장 실패: {}", e))?;
+    }
+
+    store.delete("config");
+    save_profiles(app, &profiles)?;
 
-    Ok(config)
+    Ok(profiles)
 }
 
+/// 프로필 목록 저장
+fn save_profiles(app: &AppHandle, profiles: &ProfileList) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+
+    store.set(
+        "profiles",
+        serde_json::to_value(profiles).map_err(|e| format!("직렬화 실패: {}", e))?,
+    );
+
+    store.save().map_err(|e| format!("저장 실패: {}", e))?;
+
+    Ok(())
+}
+
+/// 활성 프로필의 설정 조회
+#[tauri::command]
+#[specta::specta]
+pub async fn get_config(app: AppHandle) -> Result<FcmConfig, String> {
+    Ok(load_profiles(&app)?.active().config.clone())
+}
+
+/// 활성 프로필의 설정 저장
 #[tauri::command]
 #[specta::specta]
 pub async fn save_config(app: AppHandle, config: FcmConfig) -> Result<(), String> {
+    let mut profiles = load_profiles(&app)?;
+
+    let profile = profiles.active_mut();
+    profile.config = config;
+    profile.updated_at = Utc::now();
+
+    save_profiles(&app, &profiles)
+}
+
+// ============================================================================
+// 프로필 관련 커맨드
+// ============================================================================
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_profiles(app: AppHandle) -> Result<ProfileList, String> {
+    load_profiles(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_profile(app: AppHandle, name: String) -> Result<Profile, String> {
+    let mut profiles = load_profiles(&app)?;
+    let profile = profiles.create(&name, FcmConfig::default())?;
+    save_profiles(&app, &profiles)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn clone_profile(app: AppHandle, id: String, name: String) -> Result<Profile, String> {
+    let mut profiles = load_profiles(&app)?;
+    let profile = profiles.clone_profile(&id, &name)?;
+    save_profiles(&app, &profiles)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_profile(app: AppHandle, id: String, name: String) -> Result<(), String> {
+    let mut profiles = load_profiles(&app)?;
+    profiles.rename(&id, &name)?;
+    save_profiles(&app, &profiles)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_profile(app: AppHandle, id: String) -> Result<(), String> {
+    let mut profiles = load_profiles(&app)?;
+    profiles.delete(&id)?;
+    save_profiles(&app, &profiles)
+}
+
+/// 프로필 활성화 (기본 계정이 지정되어 있고 로그인된 상태면 해당 계정으로 전환)
+#[tauri::command]
+#[specta::specta]
+pub async fn activate_profile(app: AppHandle, id: String) -> Result<(), String> {
+    let mut profiles = load_profiles(&app)?;
+    profiles.activate(&id)?;
+    save_profiles(&app, &profiles)?;
+
+    if let Some(email) = profiles.active().default_account.as_deref() {
+        let mut accounts = load_accounts(&app)?;
+        if accounts.switch(email).is_ok() {
+            save_accounts(&app, &accounts)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 프로필의 기본 계정 지정 (None이면 해제)
+#[tauri::command]
+#[specta::specta]
+pub async fn set_profile_default_account(
+    app: AppHandle,
+    id: String,
+    email: Option<String>,
+) -> Result<(), String> {
+    if let Some(ref email) = email {
+        if load_accounts(&app)?.get(email).is_none() {
+            return Err(format!("로그인된 계정이 아닙니다: {}", email));
+        }
+    }
+
+    let mut profiles = load_profiles(&app)?;
+    profiles.set_default_account(&id, email)?;
+    save_profiles(&app, &profiles)
+}
+
+// ============================================================================
+// 토큰 저장소 관련 커맨드
+// ============================================================================
+
+const VAULT_FILE: &str = "token.vault";
+const MACHINE_KEY_FILE: &str = "vault.key";
+
+/// 토큰 저장소 설정 로드
+fn load_token_storage(app: &AppHandle) -> Result<TokenStorageSettings, String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+
+    Ok(store
+        .get("tokenStorage")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// 토큰 저장소 설정 저장
+fn save_token_storage(app: &AppHandle, settings: &TokenStorageSettings) -> Result<(), String> {
     let store = app
         .store(CONFIG_STORE)
         .map_err(|e| format!("스토어 열기 실패: {}", e))?;
 
     store.set(
-        "config",
-        serde_json::to_value(&config).map_err(|e| format!("직렬화 실패: {}", e))?,
+        "tokenStorage",
+        serde_json::to_value(settings).map_err(|e| format!("직렬화 실패: {}", e))?,
     );
 
     store.save().map_err(|e| format!("저장 실패: {}", e))?;
@@ -51,43 +232,379 @@ pub async fn save_config(app: AppHandle, config: FcmConfig) -> Result<(), String
     Ok(())
 }
 
+fn app_data_file(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(name))
+        .map_err(|e| format!("앱 데이터 경로 확인 실패: {}", e))
+}
+
+fn vault_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app_data_file(app, VAULT_FILE)
+}
+
+fn cached_vault_key(app: &AppHandle) -> Option<VaultKey> {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().ok()?;
+    state.vault_key.clone()
+}
+
+fn cache_vault_key(app: &AppHandle, key: Option<VaultKey>) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().map_err(|_| "앱 상태 잠금 실패".to_string())?;
+    state.vault_key = key;
+    Ok(())
+}
+
+/// 볼트 키 생성 또는 잠금 해제 (기기 키는 자동, 패스프레이즈는 인자로 전달)
+fn open_vault_key(
+    app: &AppHandle,
+    source: VaultKeySource,
+    passphrase: Option<&str>,
+    fresh: bool,
+) -> Result<VaultKey, String> {
+    let secret = match source {
+        VaultKeySource::Machine => load_machine_secret(&app_data_file(app, MACHINE_KEY_FILE)?)?,
+        VaultKeySource::Passphrase => passphrase
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| "패스프레이즈를 입력해주세요".to_string())?
+            .as_bytes()
+            .to_vec(),
+    };
+
+    if fresh {
+        VaultKey::create(&secret, source)
+    } else {
+        VaultKey::unlock(&vault_path(app)?, &secret, source)
+    }
+}
+
+/// 현재 설정에 맞는 잠금 해제된 볼트 키 (기기 키 방식이면 필요할 때 자동으로 잠금 해제)
+fn unlocked_vault_key(
+    app: &AppHandle,
+    settings: &TokenStorageSettings,
+) -> Result<VaultKey, String> {
+    if let Some(key) = cached_vault_key(app).filter(|k| k.source() == settings.key_source) {
+        return Ok(key);
+    }
+
+    match settings.key_source {
+        VaultKeySource::Machine => {
+            let key = open_vault_key(app, VaultKeySource::Machine, None, false)?;
+            cache_vault_key(app, Some(key.clone()))?;
+            Ok(key)
+        }
+        VaultKeySource::Passphrase => {
+            Err("토큰 볼트가 잠겨 있습니다. 패스프레이즈로 잠금을 해제해주세요.".to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_token_storage(app: AppHandle) -> Result<TokenStorageStatus, String> {
+    let settings = load_token_storage(&app)?;
+    let unlocked = settings.backend == TokenBackend::Plaintext
+        || settings.key_source == VaultKeySource::Machine
+        || cached_vault_key(&app).is_some_and(|k| k.source() == settings.key_source);
+
+    Ok(TokenStorageStatus {
+        settings,
+        unlocked,
+        vault_exists: vault_path(&app)?.exists(),
+    })
+}
+
+/// 패스프레이즈로 볼트 잠금 해제 (볼트가 없으면 이 패스프레이즈로 새로 생성)
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_vault(app: AppHandle, passphrase: String) -> Result<(), String> {
+    let key = open_vault_key(&app, VaultKeySource::Passphrase, Some(&passphrase), false)?;
+    cache_vault_key(&app, Some(key))
+}
+
+/// 메모리의 볼트 키 삭제
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_vault(app: AppHandle) -> Result<(), String> {
+    cache_vault_key(&app, None)
+}
+
+/// 토큰 저장 방식 변경 (기존 계정을 새 저장소로 옮김)
+///
+/// 패스프레이즈 방식 볼트로 바꾸거나 패스프레이즈를 변경할 때는 새 패스프레이즈를 전달한다.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_token_storage(
+    app: AppHandle,
+    settings: TokenStorageSettings,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    // 1. 현재 저장소의 계정 로드 (잠겨 있으면 실패)
+    let accounts = load_accounts(&app)?;
+    let path = vault_path(&app)?;
+
+    // 2. 새 저장소에 기록
+    match settings.backend {
+        TokenBackend::Plaintext => {
+            save_plain_accounts(&app, &accounts)?;
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| format!("볼트 파일 삭제 실패: {}", e))?;
+            }
+            cache_vault_key(&app, None)?;
+        }
+        TokenBackend::Vault => {
+            let key = open_vault_key(&app, settings.key_source, passphrase.as_deref(), true)?;
+            key.write(&path, &accounts)?;
+            clear_plain_accounts(&app)?;
+            cache_vault_key(&app, Some(key))?;
+        }
+    }
+
+    // 3. 설정 저장
+    save_token_storage(&app, &settings)
+}
+
 // ============================================================================
 // 인증 관련 커맨드
 // ============================================================================
 
-#[tauri::command]
-#[specta::specta]
-pub async fn is_authenticated(app: AppHandle) -> Result<bool, String> {
+/// 이전 버전의 프로필별 토큰 슬롯 키 접두사
+const LEGACY_TOKEN_PREFIX: &str = "token:";
+
+/// id_token에 email이 없을 때 사용할 계정 이름
+fn fallback_account_label(profile: &Profile) -> String {
+    format!("profile:{}", profile.name)
+}
+
+/// token.json의 계정 목록 로드 (프로필별 토큰 슬롯만 있던 이전 버전 데이터는 계정으로 마이그레이션)
+fn load_plain_accounts(app: &AppHandle) -> Result<AccountList, String> {
     let store = app
         .store(TOKEN_STORE)
         .map_err(|e| format!("스토어 열기 실패: {}", e))?;
 
-    let token: Option<OAuthToken> = store
-        .get("token")
-        .and_then(|v| serde_json::from_value(v).ok());
+    if let Some(accounts) = store
+        .get("accounts")
+        .and_then(|v| serde_json::from_value::<AccountList>(v).ok())
+    {
+        return Ok(accounts);
+    }
+
+    // 프로필 로드 시 단일 token 키는 프로필 토큰 슬롯으로 먼저 이동됨
+    let profiles = load_profiles(app)?;
+    let mut accounts = AccountList::default();
+
+    for key in store.keys() {
+        let Some(profile_id) = key.strip_prefix(LEGACY_TOKEN_PREFIX) else {
+            continue;
+        };
+        if let Some(token) = store
+            .get(&key)
+            .and_then(|v| serde_json::from_value::<OAuthToken>(v).ok())
+        {
+            let fallback = profiles
+                .get(profile_id)
+                .map(fallback_account_label)
+                .unwrap_or_else(|| format!("profile:{}", profile_id));
+            let email = account_email(&token, &fallback);
+            accounts.upsert(email, profile_id.to_string(), token);
+        }
+        store.delete(&key);
+    }
+
+    // 활성 프로필의 토큰이었던 계정을 활성화
+    if let Some(account) = accounts
+        .accounts
+        .iter()
+        .find(|a| a.profile_id == profiles.active_id)
+    {
+        accounts.active_email = Some(account.email.clone());
+    }
+
+    save_plain_accounts(app, &accounts)?;
 
-    Ok(token.map(|t| t.is_valid()).unwrap_or(false))
+    Ok(accounts)
 }
 
-#[tauri::command]
-#[specta::specta]
-pub async fn get_token(app: AppHandle) -> Result<Option<OAuthToken>, String> {
+/// token.json에 계정 목록 저장
+fn save_plain_accounts(app: &AppHandle, accounts: &AccountList) -> Result<(), String> {
     let store = app
         .store(TOKEN_STORE)
         .map_err(|e| format!("스토어 열기 실패: {}", e))?;
 
-    let token: Option<OAuthToken> = store
-        .get("token")
-        .and_then(|v| serde_json::from_value(v).ok());
+    store.set(
+        "accounts",
+        serde_json::to_value(accounts).map_err(|e| format!("직렬화 실패: {}", e))?,
+    );
 
-    Ok(token.filter(|t| t.is_valid()))
+    store.save().map_err(|e| format!("저장 실패: {}", e))?;
+
+    Ok(())
+}
+
+/// token.json의 계정 데이터 삭제 (볼트로 옮긴 뒤 평문이 남지 않도록)
+fn clear_plain_accounts(app: &AppHandle) -> Result<(), String> {
+    let store = app
+        .store(TOKEN_STORE)
+        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+
+    store.delete("accounts");
+    store.save().map_err(|e| format!("저장 실패: {}", e))?;
+
+    Ok(())
+}
+
+/// 로그인된 계정 목록 로드 (저장소 설정에 따라 볼트 또는 token.json)
+///
+/// 볼트가 아직 없으면 token.json의 계정을 볼트로 옮긴다.
+fn load_accounts(app: &AppHandle) -> Result<AccountList, String> {
+    let settings = load_token_storage(app)?;
+    if settings.backend == TokenBackend::Plaintext {
+        return load_plain_accounts(app);
+    }
+
+    let key = unlocked_vault_key(app, &settings)?;
+    let path = vault_path(app)?;
+    if let Some(accounts) = key.read(&path)? {
+        return Ok(accounts);
+    }
+
+    let accounts = load_plain_accounts(app)?;
+    key.write(&path, &accounts)?;
+    clear_plain_accounts(app)?;
+    logger::info("token.json의 계정을 암호화된 볼트로 이전했습니다");
+
+    Ok(accounts)
+}
+
+/// 로그인된 계정 목록 저장
+fn save_accounts(app: &AppHandle, accounts: &AccountList) -> Result<(), String> {
+    let settings = load_token_storage(app)?;
+    match settings.backend {
+        TokenBackend::Plaintext => save_plain_accounts(app, accounts),
+        TokenBackend::Vault => {
+            unlocked_vault_key(app, &settings)?.write(&vault_path(app)?, accounts)
+        }
+    }
+}
+
+/// 계정 추가(또는 토큰 교체) 후 활성화
+fn sign_in_account(
+    app: &AppHandle,
+    email: String,
+    profile_id: &str,
+    token: &OAuthToken,
+) -> Result<(), String> {
+    let mut accounts = load_accounts(app)?;
+    accounts.upsert(email, profile_id.to_string(), token.clone());
+    save_accounts(app, &accounts)
+}
+
+/// 계정 로그아웃 (이 계정을 기본 계정으로 지정한 프로필은 지정 해제)
+///
+/// Google에서 토큰을 취소한 뒤 로컬 토큰을 삭제한다. 취소에 실패해도 로컬 토큰은 삭제한다.
+async fn sign_out(app: &AppHandle, email: &str) -> Result<LogoutResult, String> {
+    let mut accounts = load_accounts(app)?;
+    let mut profiles = load_profiles(app)?;
+
+    let result = logout_account(&mut accounts, &mut profiles, email).await?;
+    for error in &result.errors {
+        logger::warn(&format!("토큰 취소 실패 ({}): {}", email, error));
+    }
+
+    save_accounts(app, &accounts)?;
+    save_profiles(app, &profiles)?;
+    Ok(result)
+}
+
+/// 활성 계정의 유효한 토큰 로드 (만료 임박 시 refresh_token으로 갱신 후 저장)
+///
+/// 갱신에는 계정이 로그인한 프로필의 설정을 사용하고, 그 프로필이 삭제된 경우 활성 프로필 설정을 사용한다.
+/// 갱신에 실패하면 기존 토큰이 아직 만료되지 않은 경우에만 그대로 사용하고, 만료되었으면 갱신 에러를 반환한다.
+async fn load_valid_token(
+    app: &AppHandle,
+    profiles: &ProfileList,
+) -> Result<Option<OAuthToken>, String> {
+    let mut accounts = load_accounts(app)?;
+    let Some(account) = accounts.active().cloned() else {
+        return Ok(None);
+    };
+
+    if !account.token.needs_refresh() {
+        return Ok(Some(account.token));
+    }
+
+    let config = &profiles
+        .get(&account.profile_id)
+        .unwrap_or_else(|| profiles.active())
+        .config;
+
+    match refresh_access_token(config, &account.token).await {
+        Ok(refreshed) => {
+            accounts.update_token(&account.email, refreshed.clone());
+            save_accounts(app, &accounts)?;
+            logger::info(&format!("액세스 토큰 갱신 완료: {}", account.email));
+            Ok(Some(refreshed))
+        }
+        Err(e) => {
+            logger::warn(&format!("액세스 토큰 갱신 실패 ({}): {}", account.email, e));
+            if account.token.is_valid() {
+                Ok(Some(account.token))
+            } else {
+                Err(format!(
+                    "액세스 토큰 갱신 실패: {}. 다시 로그인해주세요.",
+                    e
+                ))
+            }
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn is_authenticated(app: AppHandle) -> Result<bool, String> {
+    let profiles = load_profiles(&app)?;
+    Ok(load_valid_token(&app, &profiles).await?.is_some())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_token(app: AppHandle) -> Result<Option<OAuthToken>, String> {
+    let profiles = load_profiles(&app)?;
+    load_valid_token(&app, &profiles).await
+}
+
+/// 진행 중인 OAuth 인증의 취소 플래그 교체 (이전 플래그가 있으면 취소)
+fn replace_oauth_cancel(app: &AppHandle, cancel: Option<Arc<AtomicBool>>) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().map_err(|_| "앱 상태 잠금 실패".to_string())?;
+    if let Some(previous) = std::mem::replace(&mut state.oauth_cancel, cancel) {
+        previous.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+/// 완료된 OAuth 인증의 취소 플래그 제거 (다른 인증이 새로 시작된 경우 유지)
+fn clear_oauth_cancel(app: &AppHandle, cancel: &Arc<AtomicBool>) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().map_err(|_| "앱 상태 잠금 실패".to_string())?;
+    if state
+        .oauth_cancel
+        .as_ref()
+        .is_some_and(|current| Arc::ptr_eq(current, cancel))
+    {
+        state.oauth_cancel = None;
+    }
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn start_oauth(app: AppHandle) -> Result<AuthResult, String> {
-    // 1. 설정 가져오기
-    let config = get_config(app.clone()).await?;
+    // 1. 활성 프로필 설정 가져오기
+    let profiles = load_profiles(&app)?;
+    let profile = profiles.active();
+    let config = &profile.config;
 
     if !config.is_valid() {
         return Ok(AuthResult::failure(
@@ -95,82 +612,205 @@ pub async fn start_oauth(app: AppHandle) -> Result<AuthResult, String> {
         ));
     }
 
-    // 2. OAuth 인증 실행
-    let result = authenticate(
-        &config.oauth_client_id,
-        &config.oauth_redirect_url,
-        &config.exchange_code_url,
-    )
+    // 2. 취소 플래그를 등록하고 OAuth 인증 실행 (단계마다 진행 이벤트 발생)
+    let cancel = Arc::new(AtomicBool::new(false));
+    replace_oauth_cancel(&app, Some(Arc::clone(&cancel)))?;
+
+    let emitter = app.clone();
+    let result = authenticate(config, Arc::clone(&cancel), move |stage| {
+        if let Err(e) = (OAuthProgressEvent { stage }).emit(&emitter) {
+            logger::warn(&format!("OAuth 진행 이벤트 전송 실패: {}", e));
+        }
+    })
     .await;
 
-    // 3. 성공 시 토큰 저장
+    clear_oauth_cancel(&app, &cancel)?;
+
+    // 3. 성공 시 id_token의 이메일로 계정 저장 후 활성화
     if result.success {
         if let Some(ref token) = result.token {
-            let store = app
-                .store(TOKEN_STORE)
-                .map_err(|e| format!("스토어 열기 실패: {}", e))?;
-
-            store.set(
-                "token",
-                serde_json::to_value(token).map_err(|e| format!("직렬화 실패: {}", e))?,
-            );
-
-            store.save().map_err(|e| format!("저장 실패: {}", e))?;
+            let email = account_email(token, &fallback_account_label(profile));
+            sign_in_account(&app, email, &profile.id, token)?;
         }
     }
 
     Ok(result)
 }
 
+/// 진행 중인 OAuth 인증 취소 (진행 중인 인증이 없으면 false)
 #[tauri::command]
 #[specta::specta]
-pub async fn logout(app: AppHandle) -> Result<(), String> {
-    let store = app
-        .store(TOKEN_STORE)
-        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+pub async fn cancel_oauth(app: AppHandle) -> Result<bool, String> {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().map_err(|_| "앱 상태 잠금 실패".to_string())?;
+    match state.oauth_cancel.as_ref() {
+        Some(cancel) => {
+            cancel.store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
 
-    store.delete("token");
-    store.save().map_err(|e| format!("저장 실패: {}", e))?;
+#[tauri::command]
+#[specta::specta]
+pub async fn start_service_account_auth(
+    app: AppHandle,
+    key_path: String,
+) -> Result<AuthResult, String> {
+    // 1. 서비스 계정 키 로드
+    let key = match ServiceAccountKey::load(&key_path) {
+        Ok(key) => key,
+        Err(e) => return Ok(AuthResult::failure(e)),
+    };
 
-    Ok(())
+    // 2. 액세스 토큰 발급
+    let token = match authenticate_service_account(&key).await {
+        Ok(token) => token,
+        Err(e) => return Ok(AuthResult::failure(format!("토큰 발급 실패: {}", e))),
+    };
+
+    // 3. 키 파일의 프로젝트 ID와 인증 방식을 활성 프로필 설정에 반영
+    let mut profiles = load_profiles(&app)?;
+    let profile = profiles.active_mut();
+    profile.config.auth_mode = AuthMode::ServiceAccount;
+    profile.config.service_account_key_path = key_path;
+    profile.config.firebase_project_id = key.project_id;
+    profile.updated_at = Utc::now();
+    let profile_id = profile.id.clone();
+    save_profiles(&app, &profiles)?;
+
+    // 4. 서비스 계정 이메일로 계정 저장 후 활성화
+    sign_in_account(&app, key.client_email, &profile_id, &token)?;
+
+    Ok(AuthResult::success(token))
+}
+
+/// 활성 계정 로그아웃
+#[tauri::command]
+#[specta::specta]
+pub async fn logout(app: AppHandle) -> Result<LogoutResult, String> {
+    match load_accounts(&app)?.active_email {
+        Some(email) => sign_out(&app, &email).await,
+        None => Ok(LogoutResult::none()),
+    }
 }
 
 // ============================================================================
-// FCM 발송 커맨드
+// 계정 관련 커맨드
 // ============================================================================
 
 #[tauri::command]
 #[specta::specta]
-pub async fn send_fcm_message(app: AppHandle, request: SendRequest) -> Result<SendResult, String> {
-    // 1. 토큰 확인
-    let token = get_token(app.clone())
+pub async fn list_accounts(app: AppHandle) -> Result<Vec<AccountSummary>, String> {
+    Ok(load_accounts(&app)?.summaries())
+}
+
+/// 활성 계정의 id_token 클레임 조회 (id_token이 없는 계정이면 None)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_account_info(app: AppHandle) -> Result<Option<AccountInfo>, String> {
+    let accounts = load_accounts(&app)?;
+    let Some(account) = accounts.active() else {
+        return Ok(None);
+    };
+    if account.token.id_token.is_empty() {
+        return Ok(None);
+    }
+    AccountInfo::from_id_token(&account.token.id_token).map(Some)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_account(app: AppHandle, email: String) -> Result<(), String> {
+    let mut accounts = load_accounts(&app)?;
+    accounts.switch(&email)?;
+    save_accounts(&app, &accounts)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn sign_out_account(app: AppHandle, email: String) -> Result<LogoutResult, String> {
+    sign_out(&app, &email).await
+}
+
+// ============================================================================
+// FCM 발송 커맨드
+// ============================================================================
+
+/// 활성 프로필의 FCM 클라이언트 생성 (클라이언트와 프로필 ID 반환)
+async fn active_client(app: &AppHandle) -> Result<(FcmClient, String), String> {
+    // 1. 활성 프로필과 토큰 확인
+    let profiles = load_profiles(app)?;
+    let profile = profiles.active();
+    let token = load_valid_token(app, &profiles)
         .await?
         .ok_or_else(|| "인증되지 않았습니다. 먼저 로그인해주세요.".to_string())?;
 
-    // 2. 설정 가져오기
-    let config = get_config(app.clone()).await?;
+    // 2. 설정 확인
+    let config = &profile.config;
 
     if config.firebase_project_id.is_empty() {
         return Err("Firebase 프로젝트 ID가 설정되지 않았습니다.".to_string());
     }
 
-    // 3. FCM 클라이언트 생성 및 발송
-    let client = FcmClient::new(&config.firebase_project_id, &token)?;
-    let result = client.send(request.clone()).await;
+    let client = FcmClient::new(&config.firebase_project_id, &token)?
+        .with_retry_policy(config.retry.clone());
+    Ok((client, profile.id.clone()))
+}
 
-    // 4. 히스토리 저장
-    let message_type = match request.message_type {
-        crate::fcm::message::MessageType::Single => "single",
-        crate::fcm::message::MessageType::Topic => "topic",
+/// 템플릿 발송 요청이면 템플릿을 렌더링하여 제목/내용/데이터에 적용 (렌더링 에러 반환)
+fn apply_template(
+    app: &AppHandle,
+    request: &mut SendRequest,
+) -> Result<Vec<TemplateRenderError>, String> {
+    let Some(input) = request.template.clone() else {
+        return Ok(Vec::new());
     };
 
-    let entry = HistoryEntry::new(
-        message_type,
-        &request.message.title,
-        &request.message.body,
-        result.success,
-        &result.details,
-    );
+    let store = app
+        .store(TEMPLATES_STORE)
+        .map_err(|e| format!("스토어 열기 실패: {}", e))?;
+    let templates: TemplateList = store
+        .get("templates")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let template = templates
+        .get(&input.template_id)
+        .ok_or_else(|| format!("템플릿을 찾을 수 없습니다: {}", input.template_id))?;
+
+    match template.render(&input.variables) {
+        Ok(rendered) => {
+            rendered.apply_to(request);
+            Ok(Vec::new())
+        }
+        Err(errors) => Ok(errors),
+    }
+}
+
+/// raw 요청 본문 파싱
+fn parse_raw_document(document: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(document).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn send_fcm_message(
+    app: AppHandle,
+    mut request: SendRequest,
+) -> Result<SendResult, String> {
+    // 1. 템플릿 렌더링 (실패하면 발송하지 않음)
+    let template_errors = apply_template(&app, &mut request)?;
+    if !template_errors.is_empty() {
+        return Ok(SendResult::template_failure(template_errors));
+    }
+
+    // 2. FCM 클라이언트 생성 및 발송
+    let (client, profile_id) = active_client(&app).await?;
+    let result = client.send(request.clone()).await;
+
+    // 3. 히스토리 저장 (발송한 프로필로 태그)
+    let entry = HistoryEntry::new(&request, &result).with_profile(&profile_id);
 
     // 히스토리 저장 에러는 로깅하되 발송 결과에는 영향 없음
     if let Err(e) = add_history_entry(app, entry).await {
@@ -180,6 +820,108 @@ pub async fn send_fcm_message(app: AppHandle, request: SendRequest) -> Result<Se
     Ok(result)
 }
 
+/// 발송 전 로컬 검사 (페이로드 크기, 대상/데이터 키 형식, 우선순위 조합)
+///
+/// 템플릿 발송 요청이면 템플릿을 적용한 최종 요청을 검사한다.
+/// 렌더링에 실패하면 변수별 에러를 `template_errors`에 담아 반환한다.
+#[tauri::command]
+#[specta::specta]
+pub async fn lint_send_request(
+    app: AppHandle,
+    mut request: SendRequest,
+) -> Result<LintReport, String> {
+    let template_errors = apply_template(&app, &mut request)?;
+    if !template_errors.is_empty() {
+        return Ok(LintReport::template_failure(template_errors));
+    }
+
+    Ok(lint_request(&request))
+}
+
+/// 발송하지 않고 최종 HTTP 요청 미리보기 (`reveal_token`이 true면 실제 토큰 포함)
+///
+/// 템플릿 렌더링에 실패하면 변수별 에러를 `template_errors`에 담아 반환한다.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_send_request(
+    app: AppHandle,
+    mut request: SendRequest,
+    reveal_token: bool,
+) -> Result<SendPreview, String> {
+    let template_errors = apply_template(&app, &mut request)?;
+    if !template_errors.is_empty() {
+        return Ok(SendPreview::template_failure(template_errors));
+    }
+
+    let profiles = load_profiles(&app)?;
+    let project_id = &profiles.active().config.firebase_project_id;
+    if project_id.is_empty() {
+        return Err("Firebase 프로젝트 ID가 설정되지 않았습니다.".to_string());
+    }
+
+    // 토큰을 숨기는 미리보기는 로그인하지 않아도 가능
+    let token = if reveal_token {
+        Some(
+            load_valid_token(&app, &profiles)
+                .await?
+                .ok_or_else(|| "인증되지 않았습니다. 먼저 로그인해주세요.".to_string())?,
+        )
+    } else {
+        None
+    };
+
+    preview_request(
+        project_id,
+        &request,
+        token.as_ref().map(|t| t.access_token.as_str()),
+    )
+}
+
+/// raw 요청 본문(`{"message": {...}}`)을 검증 후 그대로 발송
+#[tauri::command]
+#[specta::specta]
+pub async fn send_raw_fcm_message(app: AppHandle, document: String) -> Result<SendResult, String> {
+    let document = parse_raw_document(&document)?;
+
+    let (client, profile_id) = active_client(&app).await?;
+    let result = client.send_raw(&document).await;
+
+    let entry = HistoryEntry::from_raw(&document, &result).with_profile(&profile_id);
+    if let Err(e) = add_history_entry(app, entry).await {
+        eprintln!("히스토리 저장 실패: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// raw 요청 본문 검증 (JSON 파싱 실패도 문제로 반환)
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_raw_fcm_message(document: String) -> Vec<RawIssue> {
+    match parse_raw_document(&document) {
+        Ok(document) => validate_raw_message(&document),
+        Err(e) => vec![RawIssue {
+            path: String::new(),
+            message: e,
+        }],
+    }
+}
+
+/// 구조화된 요청을 raw 요청 본문(JSON 문자열)으로 변환
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_to_raw_message(request: SendRequest) -> Result<String, String> {
+    let document = to_raw(&request)?;
+    serde_json::to_string_pretty(&document).map_err(|e| format!("직렬화 실패: {}", e))
+}
+
+/// raw 요청 본문을 구조화된 요청으로 변환
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_from_raw_message(document: String) -> Result<RawConversion, String> {
+    from_raw(&parse_raw_document(&document)?)
+}
+
 // ============================================================================
 // 템플릿 관련 커맨드
 // ============================================================================
@@ -211,6 +953,14 @@ pub async fn save_template(app: AppHandle, template: Template) -> Result<(), Str
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
 
+    template.validate_variables()?;
+
+    // 프로필이 지정되지 않은 템플릿은 활성 프로필에 속하도록 태그
+    let mut template = template;
+    if template.profile_id.is_none() {
+        template.profile_id = Some(load_profiles(&app)?.active().id.clone());
+    }
+
     templates.save(template);
 
     store.set(
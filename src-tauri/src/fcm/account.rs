@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::auth::OAuthToken;
+use super::id_token::decode_claims;
+
+/// 로그인된 계정 (id_token의 email로 구분)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredAccount {
+    pub email: String,
+    /// 로그인할 때 사용한 프로필 ID (토큰 갱신 시 해당 프로필의 OAuth 설정 사용)
+    pub profile_id: String,
+    pub token: OAuthToken,
+}
+
+/// 프론트엔드에 노출하는 계정 요약 (토큰 제외)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    pub email: String,
+    pub profile_id: String,
+    pub active: bool,
+    /// 액세스 토큰이 유효하거나 갱신 가능한지 여부
+    pub authenticated: bool,
+}
+
+/// 로그인된 계정 목록
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountList {
+    #[serde(default)]
+    pub active_email: Option<String>,
+    #[serde(default)]
+    pub accounts: Vec<StoredAccount>,
+}
+
+/// 토큰의 계정 식별자 (id_token의 email, 없으면 fallback)
+pub fn account_email(token: &OAuthToken, fallback: &str) -> String {
+    if token.id_token.is_empty() {
+        return fallback.to_string();
+    }
+    decode_claims(&token.id_token)
+        .ok()
+        .and_then(|claims| claims.email)
+        .filter(|email| !email.is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+impl AccountList {
+    /// 이메일로 계정 찾기
+    pub fn get(&self, email: &str) -> Option<&StoredAccount> {
+        self.accounts.iter().find(|a| a.email == email)
+    }
+
+    /// 활성 계정
+    pub fn active(&self) -> Option<&StoredAccount> {
+        self.active_email
+            .as_deref()
+            .and_then(|email| self.get(email))
+    }
+
+    /// 계정 추가 또는 토큰 교체 후 활성화
+    pub fn upsert(&mut self, email: String, profile_id: String, token: OAuthToken) {
+        match self.accounts.iter_mut().find(|a| a.email == email) {
+            Some(account) => {
+                account.profile_id = profile_id;
+                account.token = token;
+            }
+            None => self.accounts.push(StoredAccount {
+                email: email.clone(),
+                profile_id,
+                token,
+            }),
+        }
+        self.active_email = Some(email);
+    }
+
+    /// 기존 계정의 토큰만 교체 (갱신 결과 저장용)
+    pub fn update_token(&mut self, email: &str, token: OAuthToken) {
+        if let Some(account) = self.accounts.iter_mut().find(|a| a.email == email) {
+            account.token = token;
+        }
+    }
+
+    /// 활성 계정 전환
+    pub fn switch(&mut self, email: &str) -> Result<(), String> {
+        if self.get(email).is_none() {
+            return Err(format!("로그인된 계정이 아닙니다: {}", email));
+        }
+        self.active_email = Some(email.to_string());
+        Ok(())
+    }
+
+    /// 계정 로그아웃 (활성 계정이면 남은 첫 번째 계정 활성화)
+    pub fn remove(&mut self, email: &str) -> Result<StoredAccount, String> {
+        let index = self
+            .accounts
+            .iter()
+            .position(|a| a.email == email)
+            .ok_or_else(|| format!("로그인된 계정이 아닙니다: {}", email))?;
+        let removed = self.accounts.remove(index);
+
+        if self.active_email.as_deref() == Some(email) {
+            self.active_email = self.accounts.first().map(|a| a.email.clone());
+        }
+        Ok(removed)
+    }
+
+    /// 계정 요약 목록
+    pub fn summaries(&self) -> Vec<AccountSummary> {
+        self.accounts
+            .iter()
+            .map(|a| AccountSummary {
+                email: a.email.clone(),
+                profile_id: a.profile_id.clone(),
+                active: self.active_email.as_deref() == Some(a.email.as_str()),
+                authenticated: a.token.is_valid() || !a.token.refresh_token.is_empty(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn token(id_token: &str) -> OAuthToken {
+        OAuthToken {
+            access_token: "access".to_string(),
+            refresh_token: String::new(),
+            id_token: id_token.to_string(),
+            token_type: "Bearer".to_string(),
+            expiry: Utc::now() + Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_account_email_fallback() {
+        assert_eq!(account_email(&token(""), "profile:기본"), "profile:기본");
+        assert_eq!(account_email(&token("broken"), "fallback"), "fallback");
+    }
+
+    #[test]
+    fn test_upsert_and_switch() {
+        let mut list = AccountList::default();
+        list.upsert("a@example.com".into(), "p1".into(), token(""));
+        list.upsert("b@example.com".into(), "p1".into(), token(""));
+        assert_eq!(list.active().unwrap().email, "b@example.com");
+
+        list.upsert("a@example.com".into(), "p2".into(), token(""));
+        assert_eq!(list.accounts.len(), 2);
+        assert_eq!(list.active().unwrap().profile_id, "p2");
+
+        list.switch("b@example.com").unwrap();
+        assert!(list.switch("c@example.com").is_err());
+        assert!(list
+            .summaries()
+            .iter()
+            .any(|s| s.active && s.email == "b@example.com"));
+    }
+
+    #[test]
+    fn test_remove_reassigns_active() {
+        let mut list = AccountList::default();
+        list.upsert("a@example.com".into(), "p1".into(), token(""));
+        list.upsert("b@example.com".into(), "p1".into(), token(""));
+
+        list.remove("b@example.com").unwrap();
+        assert_eq!(list.active_email.as_deref(), Some("a@example.com"));
+        list.remove("a@example.com").unwrap();
+        assert!(list.active().is_none());
+        assert!(list.remove("a@example.com").is_err());
+    }
+}
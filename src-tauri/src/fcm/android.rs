@@ -0,0 +1,460 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::validate::{validate_analytics_label, validate_https_url};
+
+/// 메시지 최대 보관 기간 (FCM 최대값 28일)
+const MAX_TTL_SECS: u64 = 28 * 24 * 60 * 60;
+
+/// 데이터 전용 메시지의 기본 Android 우선순위 (우선순위를 지정하지 않은 경우)
+const DATA_ONLY_ANDROID_PRIORITY: &str = "normal";
+
+/// 잠금 화면 알림 공개 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AndroidVisibility {
+    Private,
+    Public,
+    Secret,
+}
+
+/// 알림 표시 우선순위 (Android 7.1 이하용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationPriority {
+    PriorityMin,
+    PriorityLow,
+    PriorityDefault,
+    PriorityHigh,
+    PriorityMax,
+}
+
+/// LED 설정
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LightSettings {
+    /// LED 색상 (#RRGGBB 또는 #RRGGBBAA)
+    pub color: String,
+    /// 켜짐 시간 (예: "0.5s")
+    pub light_on_duration: String,
+    /// 꺼짐 시간 (예: "1s")
+    pub light_off_duration: String,
+}
+
+/// Android 알림 설정
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AndroidSettings {
+    /// 우선순위: "high" 또는 "normal" (비우면 지정하지 않음)
+    pub priority: String,
+    /// 알림 채널 ID
+    pub channel_id: String,
+    /// 메시지 보관 기간 (예: "3600s", "3.5s")
+    #[serde(default)]
+    pub ttl: Option<String>,
+    /// 같은 키의 메시지는 마지막 것만 전달
+    #[serde(default)]
+    pub collapse_key: Option<String>,
+    /// 이 패키지명의 앱에만 전달
+    #[serde(default)]
+    pub restricted_package_name: Option<String>,
+    /// 기기 잠금 해제 전(direct boot 모드)에도 전달
+    #[serde(default)]
+    pub direct_boot_ok: Option<bool>,
+    /// 알림 아이콘 리소스 이름
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// 아이콘 색상 (#RRGGBB)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// 사운드: "default" 또는 raw 리소스 파일명
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// 같은 태그의 알림은 교체됨
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// 알림 클릭 시 실행할 액티비티의 intent filter
+    #[serde(default)]
+    pub click_action: Option<String>,
+    /// 알림 이미지 URL (https)
+    #[serde(default)]
+    pub image: Option<String>,
+    /// 접근성 서비스용 티커 텍스트
+    #[serde(default)]
+    pub ticker: Option<String>,
+    /// true이면 클릭해도 알림이 사라지지 않음
+    #[serde(default)]
+    pub sticky: Option<bool>,
+    #[serde(default)]
+    pub visibility: Option<AndroidVisibility>,
+    #[serde(default)]
+    pub notification_priority: Option<NotificationPriority>,
+    /// 앱 아이콘 배지 숫자
+    #[serde(default)]
+    pub notification_count: Option<u32>,
+    #[serde(default)]
+    pub light_settings: Option<LightSettings>,
+    /// 진동 패턴 (예: ["0s", "0.5s", "0.2s"])
+    #[serde(default)]
+    pub vibrate_timings: Vec<String>,
+    #[serde(default)]
+    pub title_loc_key: Option<String>,
+    #[serde(default)]
+    pub title_loc_args: Vec<String>,
+    #[serde(default)]
+    pub body_loc_key: Option<String>,
+    #[serde(default)]
+    pub body_loc_args: Vec<String>,
+    /// 알림에 표시할 이벤트 발생 시각
+    #[serde(default)]
+    pub event_time: Option<DateTime<Utc>>,
+    /// Android 전용 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+}
+
+/// Android 설정 (FCM API용)
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AndroidConfig {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    priority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collapse_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restricted_package_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direct_boot_ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notification: Option<AndroidNotification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fcm_options: Option<AndroidFcmOptions>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AndroidFcmOptions {
+    analytics_label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AndroidNotification {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sound: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    click_action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticker: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sticky: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<AndroidVisibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notification_priority: Option<NotificationPriority>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notification_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    light_settings: Option<ApiLightSettings>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    vibrate_timings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_loc_key: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    title_loc_args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body_loc_key: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    body_loc_args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_time: Option<String>,
+}
+
+/// LED 설정 (FCM API용, 색상은 google.type.Color)
+#[derive(Debug, Clone, Serialize)]
+struct ApiLightSettings {
+    color: ApiColor,
+    light_on_duration: String,
+    light_off_duration: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ApiColor {
+    red: f32,
+    green: f32,
+    blue: f32,
+    alpha: f32,
+}
+
+/// 데이터 전용 메시지의 Android 설정 (알림 블록 없이 전달 옵션만 사용)
+///
+/// 우선순위를 지정하지 않았으면 normal로 보내고, 지정했으면 그대로 유지한다.
+pub(crate) fn data_only_config(
+    settings: Option<&AndroidSettings>,
+) -> Result<AndroidConfig, String> {
+    let mut config = match settings {
+        Some(settings) => settings.to_config()?,
+        None => AndroidConfig {
+            priority: String::new(),
+            ttl: None,
+            collapse_key: None,
+            restricted_package_name: None,
+            direct_boot_ok: None,
+            notification: None,
+            fcm_options: None,
+        },
+    };
+    if config.priority.is_empty() {
+        config.priority = DATA_ONLY_ANDROID_PRIORITY.to_string();
+    }
+    config.notification = None;
+    Ok(config)
+}
+
+impl AndroidSettings {
+    /// 각 필드 형식 검증
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.priority.as_str(), "" | "high" | "normal") {
+            return Err(format!(
+                "android.priority: \"high\" 또는 \"normal\"이어야 합니다 (현재 \"{}\")",
+                self.priority
+            ));
+        }
+        if let Some(ttl) = &self.ttl {
+            let secs = parse_duration(ttl).map_err(|e| format!("android.ttl: {}", e))?;
+            if secs > MAX_TTL_SECS as f64 {
+                return Err(format!(
+                    "android.ttl: 최대 {}s (28일)까지 가능합니다",
+                    MAX_TTL_SECS
+                ));
+            }
+        }
+        if let Some(color) = &self.color {
+            parse_hex_color(color, false).map_err(|e| format!("android.color: {}", e))?;
+        }
+        if let Some(image) = &self.image {
+            validate_https_url(image).map_err(|e| format!("android.image: {}", e))?;
+        }
+        if let Some(light) = &self.light_settings {
+            parse_hex_color(&light.color, true)
+                .map_err(|e| format!("android.lightSettings.color: {}", e))?;
+            parse_duration(&light.light_on_duration)
+                .map_err(|e| format!("android.lightSettings.lightOnDuration: {}", e))?;
+            parse_duration(&light.light_off_duration)
+                .map_err(|e| format!("android.lightSettings.lightOffDuration: {}", e))?;
+        }
+        for (i, timing) in self.vibrate_timings.iter().enumerate() {
+            parse_duration(timing).map_err(|e| format!("android.vibrateTimings[{}]: {}", i, e))?;
+        }
+        if !self.title_loc_args.is_empty() && self.title_loc_key.is_none() {
+            return Err("android.titleLocArgs: titleLocKey가 필요합니다".to_string());
+        }
+        if !self.body_loc_args.is_empty() && self.body_loc_key.is_none() {
+            return Err("android.bodyLocArgs: bodyLocKey가 필요합니다".to_string());
+        }
+        if let Some(label) = &self.analytics_label {
+            validate_analytics_label(label)
+                .map_err(|e| format!("android.analyticsLabel: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// 검증 후 FCM API용 설정으로 변환
+    pub(crate) fn to_config(&self) -> Result<AndroidConfig, String> {
+        self.validate()?;
+
+        let light_settings = match &self.light_settings {
+            Some(light) => {
+                let [red, green, blue, alpha] = parse_hex_color(&light.color, true)?;
+                Some(ApiLightSettings {
+                    color: ApiColor {
+                        red,
+                        green,
+                        blue,
+                        alpha,
+                    },
+                    light_on_duration: light.light_on_duration.clone(),
+                    light_off_duration: light.light_off_duration.clone(),
+                })
+            }
+            None => None,
+        };
+
+        let notification = AndroidNotification {
+            channel_id: non_empty(&self.channel_id),
+            icon: self.icon.clone(),
+            color: self.color.clone(),
+            sound: self.sound.clone(),
+            tag: self.tag.clone(),
+            click_action: self.click_action.clone(),
+            image: self.image.clone(),
+            ticker: self.ticker.clone(),
+            sticky: self.sticky,
+            visibility: self.visibility,
+            notification_priority: self.notification_priority,
+            notification_count: self.notification_count,
+            light_settings,
+            vibrate_timings: self.vibrate_timings.clone(),
+            title_loc_key: self.title_loc_key.clone(),
+            title_loc_args: self.title_loc_args.clone(),
+            body_loc_key: self.body_loc_key.clone(),
+            body_loc_args: self.body_loc_args.clone(),
+            event_time: self
+                .event_time
+                .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)),
+        };
+
+        Ok(AndroidConfig {
+            priority: self.priority.clone(),
+            ttl: self.ttl.clone(),
+            collapse_key: self.collapse_key.clone(),
+            restricted_package_name: self.restricted_package_name.clone(),
+            direct_boot_ok: self.direct_boot_ok,
+            notification: Some(notification),
+            fcm_options: self
+                .analytics_label
+                .clone()
+                .map(|analytics_label| AndroidFcmOptions { analytics_label }),
+        })
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// protobuf Duration 문자열 파싱 (예: "3.5s" → 3.5, 소수점 이하 최대 9자리)
+pub(crate) fn parse_duration(value: &str) -> Result<f64, String> {
+    let invalid = || format!("\"{}\"는 올바른 기간 형식이 아닙니다 (예: \"3.5s\")", value);
+
+    let number = value.strip_suffix('s').ok_or_else(invalid)?;
+    let (secs, nanos) = number.split_once('.').unwrap_or((number, ""));
+    if secs.is_empty()
+        || !secs.chars().all(|c| c.is_ascii_digit())
+        || nanos.len() > 9
+        || !nanos.chars().all(|c| c.is_ascii_digit())
+        || (number.contains('.') && nanos.is_empty())
+    {
+        return Err(invalid());
+    }
+
+    number.parse().map_err(|_| invalid())
+}
+
+/// "#RRGGBB"(또는 alpha 허용 시 "#RRGGBBAA") 색상을 0~1 범위 RGBA로 변환
+pub(crate) fn parse_hex_color(value: &str, allow_alpha: bool) -> Result<[f32; 4], String> {
+    let invalid = || {
+        if allow_alpha {
+            format!("\"{}\"는 #RRGGBB 또는 #RRGGBBAA 형식이어야 합니다", value)
+        } else {
+            format!("\"{}\"는 #RRGGBB 형식이어야 합니다", value)
+        }
+    };
+
+    let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+    if !(hex.len() == 6 || (allow_alpha && hex.len() == 8))
+        || !hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(invalid());
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map(|v| v as f32 / 255.0);
+    let alpha = if hex.len() == 8 { channel(6) } else { Ok(1.0) };
+    match (channel(0), channel(2), channel(4), alpha) {
+        (Ok(r), Ok(g), Ok(b), Ok(a)) => Ok([r, g, b, a]),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> AndroidSettings {
+        serde_json::from_value(serde_json::json!({
+            "priority": "high",
+            "channelId": "default"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("3.5s").unwrap(), 3.5);
+        assert_eq!(parse_duration("3600s").unwrap(), 3600.0);
+        assert!(parse_duration("3.5").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("1.s").is_err());
+        assert!(parse_duration("1.0000000001s").is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(
+            parse_hex_color("#ff0000", false).unwrap(),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(parse_hex_color("#00000000", true).unwrap()[3], 0.0);
+        assert!(parse_hex_color("#00000000", false).is_err());
+        assert!(parse_hex_color("ff0000", false).is_err());
+        assert!(parse_hex_color("#gg0000", false).is_err());
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        assert!(settings().validate().is_ok());
+
+        let mut s = settings();
+        s.ttl = Some("2419201s".to_string());
+        assert!(s.validate().unwrap_err().starts_with("android.ttl"));
+
+        let mut s = settings();
+        s.priority = "urgent".to_string();
+        assert!(s.validate().is_err());
+
+        let mut s = settings();
+        s.body_loc_args = vec!["a".to_string()];
+        assert!(s.validate().is_err());
+
+        let mut s = settings();
+        s.image = Some("http://example.com/a.png".to_string());
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_payload() {
+        let mut s = settings();
+        s.ttl = Some("60s".to_string());
+        s.visibility = Some(AndroidVisibility::Public);
+        s.notification_priority = Some(NotificationPriority::PriorityHigh);
+        s.light_settings = Some(LightSettings {
+            color: "#00ff00".to_string(),
+            light_on_duration: "0.5s".to_string(),
+            light_off_duration: "1s".to_string(),
+        });
+
+        let json = serde_json::to_value(s.to_config().unwrap()).unwrap();
+        assert_eq!(json["ttl"], "60s");
+        assert_eq!(json["notification"]["channel_id"], "default");
+        assert_eq!(json["notification"]["visibility"], "PUBLIC");
+        assert_eq!(
+            json["notification"]["notification_priority"],
+            "PRIORITY_HIGH"
+        );
+        assert_eq!(
+            json["notification"]["light_settings"]["color"]["green"],
+            1.0
+        );
+        assert!(json["notification"].get("icon").is_none());
+    }
+}
@@ -0,0 +1,526 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::validate::{validate_analytics_label, validate_https_url};
+
+/// APNs 페이로드 최대 크기 (바이트)
+pub const MAX_APNS_PAYLOAD_BYTES: usize = 4096;
+
+/// apns-collapse-id 최대 길이 (바이트)
+const MAX_COLLAPSE_ID_BYTES: usize = 64;
+
+/// background 푸시의 APNs 우선순위 (APNs는 background 푸시에 10을 허용하지 않음)
+const BACKGROUND_APNS_PRIORITY: &str = "5";
+
+/// apns-push-type 헤더 값
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ApnsPushType {
+    Alert,
+    Background,
+    Voip,
+    Complication,
+    Fileprovider,
+    Mdm,
+    Location,
+    Liveactivity,
+}
+
+impl ApnsPushType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::Background => "background",
+            Self::Voip => "voip",
+            Self::Complication => "complication",
+            Self::Fileprovider => "fileprovider",
+            Self::Mdm => "mdm",
+            Self::Location => "location",
+            Self::Liveactivity => "liveactivity",
+        }
+    }
+}
+
+/// 알림 중단 수준 (iOS 15+)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum InterruptionLevel {
+    Passive,
+    Active,
+    TimeSensitive,
+    Critical,
+}
+
+/// 중요 알림 사운드 (aps.sound 딕셔너리)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalSound {
+    /// true이면 무음 모드에서도 재생 (Critical Alerts 권한 필요)
+    pub critical: bool,
+    /// 사운드 파일명 또는 "default"
+    pub name: String,
+    /// 볼륨 (0.0 ~ 1.0)
+    #[serde(default)]
+    pub volume: Option<f32>,
+}
+
+/// APNs (iOS) 알림 설정
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApnsSettings {
+    /// 우선순위: "10" (high) 또는 "5" (normal) (비우면 지정하지 않음)
+    pub priority: String,
+    /// 사운드: "default" 또는 커스텀 사운드 파일명
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// 중요 알림 사운드 (sound와 함께 사용할 수 없음)
+    #[serde(default)]
+    pub critical_sound: Option<CriticalSound>,
+    /// 앱 아이콘 배지 숫자 (0이면 배지 제거)
+    #[serde(default)]
+    pub badge: Option<u32>,
+    /// 알림 카테고리 (액션 버튼 구성)
+    #[serde(default)]
+    pub category: Option<String>,
+    /// 알림 그룹 ID
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// Notification Service Extension에서 내용 수정 허용
+    #[serde(default)]
+    pub mutable_content: Option<bool>,
+    /// 백그라운드에서 앱 깨우기
+    #[serde(default)]
+    pub content_available: Option<bool>,
+    #[serde(default)]
+    pub interruption_level: Option<InterruptionLevel>,
+    /// 알림 요약 정렬 점수 (0.0 ~ 1.0)
+    #[serde(default)]
+    pub relevance_score: Option<f64>,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    pub title_loc_key: Option<String>,
+    #[serde(default)]
+    pub title_loc_args: Vec<String>,
+    #[serde(default)]
+    pub subtitle_loc_key: Option<String>,
+    #[serde(default)]
+    pub subtitle_loc_args: Vec<String>,
+    /// 본문 로컬라이즈 키 (aps.alert.loc-key)
+    #[serde(default)]
+    pub loc_key: Option<String>,
+    #[serde(default)]
+    pub loc_args: Vec<String>,
+    #[serde(default)]
+    pub push_type: Option<ApnsPushType>,
+    /// 만료 시각 (UNIX 초, 0이면 즉시 전달 실패 시 폐기)
+    #[serde(default)]
+    pub expiration: Option<u32>,
+    /// 같은 ID의 알림은 하나로 합쳐짐 (최대 64바이트)
+    #[serde(default)]
+    pub collapse_id: Option<String>,
+    /// 앱 번들 ID (apns-topic)
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// aps 외 최상위 커스텀 키 (값은 JSON 리터럴, 파싱할 수 없으면 문자열로 취급)
+    #[serde(default)]
+    pub custom_data: BTreeMap<String, String>,
+    /// iOS 알림 이미지 URL (https, Notification Service Extension 필요)
+    #[serde(default)]
+    pub image: Option<String>,
+    /// APNs 전용 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+}
+
+/// APNs 설정 (FCM API용)
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ApnsConfig {
+    headers: BTreeMap<String, String>,
+    payload: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fcm_options: Option<ApnsFcmOptions>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ApnsFcmOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analytics_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+}
+
+impl ApnsConfig {
+    /// 기기에 전달되는 페이로드 크기 추정
+    ///
+    /// FCM이 notification의 제목/내용과 data를 APNs 페이로드에 합치므로 함께 계산한다.
+    pub(crate) fn estimated_size(
+        &self,
+        notification: Option<(&str, &str)>,
+        data: &BTreeMap<String, String>,
+    ) -> usize {
+        let mut payload = self.payload.clone();
+        if let Some((title, body)) = notification {
+            let aps = payload
+                .entry("aps")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Some(aps) = aps.as_object_mut() {
+                let alert = aps
+                    .entry("alert")
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Some(alert) = alert.as_object_mut() {
+                    alert.entry("title").or_insert_with(|| json!(title));
+                    alert.entry("body").or_insert_with(|| json!(body));
+                }
+            }
+        }
+        for (key, value) in data {
+            payload.entry(key.clone()).or_insert_with(|| json!(value));
+        }
+        serde_json::to_vec(&payload).map(|v| v.len()).unwrap_or(0)
+    }
+
+    /// 크기 제한을 넘으면 경고 메시지
+    pub(crate) fn size_warning(
+        &self,
+        notification: Option<(&str, &str)>,
+        data: &BTreeMap<String, String>,
+    ) -> Option<String> {
+        let size = self.estimated_size(notification, data);
+        (size > MAX_APNS_PAYLOAD_BYTES).then(|| {
+            format!(
+                "APNs 페이로드가 {}바이트로 제한({}바이트)을 넘어 iOS 기기에 전달되지 않을 수 있습니다",
+                size, MAX_APNS_PAYLOAD_BYTES
+            )
+        })
+    }
+}
+
+/// 데이터 전용 메시지의 APNs 설정 (백그라운드 전달용 헤더와 content-available만 사용)
+///
+/// APNs는 우선순위 10의 background 푸시를 거부하므로 지정한 우선순위와 관계없이 5로 보낸다.
+pub(crate) fn data_only_config(settings: Option<&ApnsSettings>) -> Result<ApnsConfig, String> {
+    let mut headers = match settings {
+        Some(settings) => {
+            settings.validate()?;
+            settings.headers()
+        }
+        None => BTreeMap::new(),
+    };
+    headers.insert(
+        "apns-priority".to_string(),
+        BACKGROUND_APNS_PRIORITY.to_string(),
+    );
+    headers.insert("apns-push-type".to_string(), "background".to_string());
+
+    let mut payload = match settings {
+        Some(settings) => settings.custom_payload(),
+        None => Map::new(),
+    };
+    payload.insert("aps".to_string(), json!({ "content-available": 1 }));
+
+    // 이미지는 알림 표시용이므로 분석 라벨만 유지
+    let fcm_options = settings
+        .and_then(|s| s.analytics_label.clone())
+        .map(|analytics_label| ApnsFcmOptions {
+            analytics_label: Some(analytics_label),
+            image: None,
+        });
+
+    Ok(ApnsConfig {
+        headers,
+        payload,
+        fcm_options,
+    })
+}
+
+impl ApnsSettings {
+    /// 각 필드 형식 검증
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.priority.as_str(), "" | "10" | "5") {
+            return Err(format!(
+                "apns.priority: \"10\" 또는 \"5\"이어야 합니다 (현재 \"{}\")",
+                self.priority
+            ));
+        }
+        if self.sound.is_some() && self.critical_sound.is_some() {
+            return Err("apns.criticalSound: sound와 함께 사용할 수 없습니다".to_string());
+        }
+        if let Some(volume) = self.critical_sound.as_ref().and_then(|s| s.volume) {
+            if !(0.0..=1.0).contains(&volume) {
+                return Err("apns.criticalSound.volume: 0.0 ~ 1.0 범위여야 합니다".to_string());
+            }
+        }
+        if let Some(score) = self.relevance_score {
+            if !(0.0..=1.0).contains(&score) {
+                return Err("apns.relevanceScore: 0.0 ~ 1.0 범위여야 합니다".to_string());
+            }
+        }
+        if self
+            .collapse_id
+            .as_ref()
+            .is_some_and(|id| id.len() > MAX_COLLAPSE_ID_BYTES)
+        {
+            return Err(format!(
+                "apns.collapseId: 최대 {}바이트까지 가능합니다",
+                MAX_COLLAPSE_ID_BYTES
+            ));
+        }
+        if self.push_type == Some(ApnsPushType::Background)
+            && !self.priority.is_empty()
+            && self.priority != BACKGROUND_APNS_PRIORITY
+        {
+            return Err("apns.priority: background 푸시는 \"5\"이어야 합니다".to_string());
+        }
+        if !self.title_loc_args.is_empty() && self.title_loc_key.is_none() {
+            return Err("apns.titleLocArgs: titleLocKey가 필요합니다".to_string());
+        }
+        if !self.subtitle_loc_args.is_empty() && self.subtitle_loc_key.is_none() {
+            return Err("apns.subtitleLocArgs: subtitleLocKey가 필요합니다".to_string());
+        }
+        if !self.loc_args.is_empty() && self.loc_key.is_none() {
+            return Err("apns.locArgs: locKey가 필요합니다".to_string());
+        }
+        if self.custom_data.contains_key("aps") {
+            return Err("apns.customData: \"aps\" 키는 사용할 수 없습니다".to_string());
+        }
+        if let Some(image) = &self.image {
+            validate_https_url(image).map_err(|e| format!("apns.image: {}", e))?;
+        }
+        if let Some(label) = &self.analytics_label {
+            validate_analytics_label(label).map_err(|e| format!("apns.analyticsLabel: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// 검증 후 FCM API용 설정으로 변환
+    pub(crate) fn to_config(&self) -> Result<ApnsConfig, String> {
+        self.validate()?;
+
+        let mut headers = self.headers();
+        if !self.priority.is_empty() {
+            headers.insert("apns-priority".to_string(), self.priority.clone());
+        } else if self.push_type == Some(ApnsPushType::Background) {
+            // 헤더가 없으면 APNs는 10으로 처리하므로 background 푸시는 5를 명시
+            headers.insert(
+                "apns-priority".to_string(),
+                BACKGROUND_APNS_PRIORITY.to_string(),
+            );
+        }
+        if let Some(push_type) = self.push_type {
+            headers.insert("apns-push-type".to_string(), push_type.as_str().to_string());
+        }
+
+        let mut payload = self.custom_payload();
+        payload.insert("aps".to_string(), Value::Object(self.aps()));
+
+        let fcm_options =
+            (self.analytics_label.is_some() || self.image.is_some()).then(|| ApnsFcmOptions {
+                analytics_label: self.analytics_label.clone(),
+                image: self.image.clone(),
+            });
+
+        Ok(ApnsConfig {
+            headers,
+            payload,
+            fcm_options,
+        })
+    }
+
+    /// 우선순위/푸시 타입 외 헤더
+    fn headers(&self) -> BTreeMap<String, String> {
+        let mut headers = BTreeMap::new();
+        if let Some(expiration) = self.expiration {
+            headers.insert("apns-expiration".to_string(), expiration.to_string());
+        }
+        if let Some(collapse_id) = &self.collapse_id {
+            headers.insert("apns-collapse-id".to_string(), collapse_id.clone());
+        }
+        if let Some(topic) = &self.topic {
+            headers.insert("apns-topic".to_string(), topic.clone());
+        }
+        headers
+    }
+
+    /// aps 딕셔너리
+    fn aps(&self) -> Map<String, Value> {
+        let mut aps = Map::new();
+
+        let mut alert = Map::new();
+        insert_opt(&mut alert, "subtitle", &self.subtitle);
+        insert_opt(&mut alert, "title-loc-key", &self.title_loc_key);
+        insert_list(&mut alert, "title-loc-args", &self.title_loc_args);
+        insert_opt(&mut alert, "subtitle-loc-key", &self.subtitle_loc_key);
+        insert_list(&mut alert, "subtitle-loc-args", &self.subtitle_loc_args);
+        insert_opt(&mut alert, "loc-key", &self.loc_key);
+        insert_list(&mut alert, "loc-args", &self.loc_args);
+        if !alert.is_empty() {
+            aps.insert("alert".to_string(), Value::Object(alert));
+        }
+
+        if let Some(sound) = &self.sound {
+            aps.insert("sound".to_string(), json!(sound));
+        }
+        if let Some(critical) = &self.critical_sound {
+            let mut sound = Map::new();
+            sound.insert("critical".to_string(), json!(u8::from(critical.critical)));
+            sound.insert("name".to_string(), json!(critical.name));
+            if let Some(volume) = critical.volume {
+                sound.insert("volume".to_string(), json!(volume));
+            }
+            aps.insert("sound".to_string(), Value::Object(sound));
+        }
+        if let Some(badge) = self.badge {
+            aps.insert("badge".to_string(), json!(badge));
+        }
+        insert_opt(&mut aps, "category", &self.category);
+        insert_opt(&mut aps, "thread-id", &self.thread_id);
+        if self.mutable_content == Some(true) {
+            aps.insert("mutable-content".to_string(), json!(1));
+        }
+        if self.content_available == Some(true) {
+            aps.insert("content-available".to_string(), json!(1));
+        }
+        if let Some(level) = self.interruption_level {
+            aps.insert("interruption-level".to_string(), json!(level));
+        }
+        if let Some(score) = self.relevance_score {
+            aps.insert("relevance-score".to_string(), json!(score));
+        }
+
+        aps
+    }
+
+    /// 최상위 커스텀 키
+    fn custom_payload(&self) -> Map<String, Value> {
+        self.custom_data
+            .iter()
+            .map(|(key, value)| {
+                let value = serde_json::from_str(value).unwrap_or_else(|_| json!(value));
+                (key.clone(), value)
+            })
+            .collect()
+    }
+}
+
+fn insert_opt(map: &mut Map<String, Value>, key: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        map.insert(key.to_string(), json!(value));
+    }
+}
+
+fn insert_list(map: &mut Map<String, Value>, key: &str, values: &[String]) {
+    if !values.is_empty() {
+        map.insert(key.to_string(), json!(values));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ApnsSettings {
+        serde_json::from_value(json!({ "priority": "10" })).unwrap()
+    }
+
+    #[test]
+    fn test_full_payload() {
+        let mut s = settings();
+        s.badge = Some(3);
+        s.thread_id = Some("chat".to_string());
+        s.mutable_content = Some(true);
+        s.interruption_level = Some(InterruptionLevel::TimeSensitive);
+        s.loc_key = Some("NEW_MESSAGE".to_string());
+        s.loc_args = vec!["Alice".to_string()];
+        s.push_type = Some(ApnsPushType::Alert);
+        s.collapse_id = Some("chat-1".to_string());
+        s.custom_data = BTreeMap::from([
+            ("count".to_string(), "5".to_string()),
+            ("label".to_string(), "plain text".to_string()),
+        ]);
+
+        let json = serde_json::to_value(s.to_config().unwrap()).unwrap();
+        assert_eq!(json["headers"]["apns-priority"], "10");
+        assert_eq!(json["headers"]["apns-push-type"], "alert");
+        assert_eq!(json["headers"]["apns-collapse-id"], "chat-1");
+        assert_eq!(json["payload"]["aps"]["badge"], 3);
+        assert_eq!(json["payload"]["aps"]["thread-id"], "chat");
+        assert_eq!(json["payload"]["aps"]["mutable-content"], 1);
+        assert_eq!(
+            json["payload"]["aps"]["interruption-level"],
+            "time-sensitive"
+        );
+        assert_eq!(json["payload"]["aps"]["alert"]["loc-args"][0], "Alice");
+        assert_eq!(json["payload"]["count"], 5);
+        assert_eq!(json["payload"]["label"], "plain text");
+    }
+
+    #[test]
+    fn test_critical_sound() {
+        let mut s = settings();
+        s.critical_sound = Some(CriticalSound {
+            critical: true,
+            name: "alarm.caf".to_string(),
+            volume: Some(0.5),
+        });
+        let json = serde_json::to_value(s.to_config().unwrap()).unwrap();
+        assert_eq!(json["payload"]["aps"]["sound"]["critical"], 1);
+        assert_eq!(json["payload"]["aps"]["sound"]["volume"], 0.5);
+
+        s.sound = Some("default".to_string());
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut s = settings();
+        s.relevance_score = Some(1.5);
+        assert!(s.validate().is_err());
+
+        let mut s = settings();
+        s.push_type = Some(ApnsPushType::Background);
+        assert!(s.validate().is_err());
+
+        // 우선순위를 지정하지 않은 background 푸시는 5로 전송
+        s.priority = String::new();
+        let json = serde_json::to_value(s.to_config().unwrap()).unwrap();
+        assert_eq!(json["headers"]["apns-priority"], "5");
+        let json = serde_json::to_value(data_only_config(Some(&s)).unwrap()).unwrap();
+        assert_eq!(json["headers"]["apns-priority"], "5");
+
+        let mut s = settings();
+        s.custom_data = BTreeMap::from([("aps".to_string(), "{}".to_string())]);
+        assert!(s.validate().is_err());
+
+        let mut s = settings();
+        s.image = Some("ftp://example.com/a.png".to_string());
+        assert!(s.validate().unwrap_err().starts_with("apns.image"));
+    }
+
+    #[test]
+    fn test_fcm_options() {
+        let mut s = settings();
+        s.image = Some("https://example.com/a.png".to_string());
+        s.analytics_label = Some("ios_launch".to_string());
+        let json = serde_json::to_value(s.to_config().unwrap()).unwrap();
+        assert_eq!(json["fcm_options"]["image"], "https://example.com/a.png");
+        assert_eq!(json["fcm_options"]["analytics_label"], "ios_launch");
+
+        let json = serde_json::to_value(data_only_config(Some(&s)).unwrap()).unwrap();
+        assert!(json["fcm_options"].get("image").is_none());
+        assert_eq!(json["fcm_options"]["analytics_label"], "ios_launch");
+    }
+
+    #[test]
+    fn test_size_warning() {
+        let config = settings().to_config().unwrap();
+        assert!(config
+            .size_warning(Some(("t", "b")), &BTreeMap::new())
+            .is_none());
+
+        let data = BTreeMap::from([("blob".to_string(), "x".repeat(MAX_APNS_PAYLOAD_BYTES))]);
+        assert!(config.size_warning(Some(("t", "b")), &data).is_some());
+    }
+}
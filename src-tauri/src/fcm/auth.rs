@@ -1,17 +1,33 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::io::{BufRead, BufReader, Write};
-use std::net::TcpListener;
-use std::sync::mpsc;
-use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use super::exchange::{exchange_code_via_external_api, ExchangeCodeResponse};
+use super::account::AccountList;
+use super::callback::{CallbackListener, CANCELLED_MESSAGE};
+use super::config::{AuthMode, ExchangeMode, FcmConfig};
+use super::exchange::{
+    exchange_code_direct, exchange_code_via_external_api, refresh_token_direct,
+    refresh_token_via_external_api, revoke_token, ExchangeCodeResponse,
+};
+use super::id_token::AccountInfo;
 use super::pkce::{generate_code_challenge, generate_code_verifier, generate_state};
+use super::profile::ProfileList;
+use super::service_account::{authenticate_service_account, ServiceAccountKey};
 
-const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+pub(crate) const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+/// 계정 식별용 id_token(email 클레임)을 받기 위한 OpenID 스코프
+const IDENTITY_SCOPES: &str = "openid email profile";
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 
+/// relay 교환 모드에서 토큰 갱신 API URL이 없을 때의 에러 메시지
+const REFRESH_NOT_CONFIGURED_MESSAGE: &str =
+    "토큰 갱신 API URL이 설정되지 않아 토큰을 갱신할 수 없습니다";
+
+/// 만료 전 미리 갱신을 시작하는 여유 시간 (초)
+const REFRESH_MARGIN_SECS: i64 = 300;
+
 /// OAuth 토큰 정보
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +47,24 @@ impl OAuthToken {
         !self.access_token.is_empty() && Utc::now() < self.expiry
     }
 
+    /// 만료가 임박했거나 이미 만료되어 갱신이 필요한지 확인
+    pub fn needs_refresh(&self) -> bool {
+        self.access_token.is_empty()
+            || Utc::now() + Duration::seconds(REFRESH_MARGIN_SECS) >= self.expiry
+    }
+
+    /// 갱신 응답으로 새 토큰 생성 (응답에 없는 refresh_token/id_token은 유지)
+    pub fn refreshed(&self, resp: ExchangeCodeResponse) -> Self {
+        let mut token = Self::from_response(resp);
+        if token.refresh_token.is_empty() {
+            token.refresh_token = self.refresh_token.clone();
+        }
+        if token.id_token.is_empty() {
+            token.id_token = self.id_token.clone();
+        }
+        token
+    }
+
     /// ExchangeCodeResponse로부터 OAuthToken 생성
     pub fn from_response(resp: ExchangeCodeResponse) -> Self {
         let expiry = Utc::now() + Duration::seconds(resp.expires_in);
@@ -44,6 +78,42 @@ impl OAuthToken {
     }
 }
 
+/// 액세스 토큰 갱신
+///
+/// 서비스 계정 모드는 키로 새 토큰을 발급받고, OAuth 모드는 refresh_token을 사용한다.
+pub async fn refresh_access_token(
+    config: &FcmConfig,
+    token: &OAuthToken,
+) -> Result<OAuthToken, String> {
+    if config.auth_mode == AuthMode::ServiceAccount {
+        let key = ServiceAccountKey::load(&config.service_account_key_path)?;
+        return authenticate_service_account(&key).await;
+    }
+
+    if token.refresh_token.is_empty() {
+        return Err("refresh_token이 없습니다".to_string());
+    }
+
+    let response = match config.exchange_mode {
+        ExchangeMode::Relay => {
+            if config.refresh_token_url.is_empty() {
+                return Err(REFRESH_NOT_CONFIGURED_MESSAGE.to_string());
+            }
+            refresh_token_via_external_api(&config.refresh_token_url, &token.refresh_token).await?
+        }
+        ExchangeMode::Direct => {
+            refresh_token_direct(
+                &config.token_url,
+                &config.oauth_client_id,
+                &config.oauth_client_secret,
+                &token.refresh_token,
+            )
+            .await?
+        }
+    };
+    Ok(token.refreshed(response))
+}
+
 /// OAuth 인증 결과
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -52,14 +122,25 @@ pub struct AuthResult {
     pub message: String,
     #[serde(default)]
     pub token: Option<OAuthToken>,
+    /// id_token에서 읽은 계정 정보 (서비스 계정 등 id_token이 없으면 None)
+    #[serde(default)]
+    pub account: Option<AccountInfo>,
+    /// 인증은 성공했지만 확인이 필요한 사항 (허용되지 않은 도메인 등)
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 impl AuthResult {
     pub fn success(token: OAuthToken) -> Self {
+        let account = Some(&token.id_token)
+            .filter(|t| !t.is_empty())
+            .and_then(|t| AccountInfo::from_id_token(t).ok());
         Self {
             success: true,
             message: "인증 성공".to_string(),
             token: Some(token),
+            account,
+            warnings: Vec::new(),
         }
     }
 
@@ -68,10 +149,97 @@ impl AuthResult {
             success: false,
             message,
             token: None,
+            account: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 계정의 호스팅 도메인이 허용 목록에 없으면 경고 추가
+    pub fn check_domain(mut self, allowed_domains: &[String]) -> Self {
+        if let Some(warning) = self
+            .account
+            .as_ref()
+            .and_then(|account| account.domain_warning(allowed_domains))
+        {
+            self.warnings.push(warning);
+        }
+        self
+    }
+}
+
+/// 로그아웃 결과
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutResult {
+    /// 로그아웃한 계정 (로그인된 계정이 없었으면 None)
+    pub email: Option<String>,
+    /// 모든 토큰 취소 성공 여부 (취소할 토큰이 없으면 true)
+    pub revoked: bool,
+    /// 토큰 취소 실패 메시지 (실패해도 로컬 토큰은 삭제됨)
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl LogoutResult {
+    /// 로그인된 계정이 없을 때
+    pub fn none() -> Self {
+        Self {
+            email: None,
+            revoked: true,
+            errors: Vec::new(),
         }
     }
 }
 
+/// refresh_token과 access_token을 취소하고 실패 메시지 목록 반환
+pub async fn revoke_tokens(revoke_url: &str, token: &OAuthToken) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let targets = [
+        ("refresh_token", &token.refresh_token),
+        ("access_token", &token.access_token),
+    ];
+    for (name, value) in targets {
+        if value.is_empty() {
+            continue;
+        }
+        if let Err(e) = revoke_token(revoke_url, value).await {
+            errors.push(format!("{}: {}", name, e));
+        }
+    }
+
+    errors
+}
+
+/// 계정 로그아웃 (이 계정을 기본 계정으로 지정한 프로필은 지정 해제)
+///
+/// 계정이 로그인한 프로필의 취소 URL로 토큰을 취소한 뒤 로컬 계정을 삭제한다. 취소에 실패해도 로컬 계정은 삭제한다.
+pub async fn logout_account(
+    accounts: &mut AccountList,
+    profiles: &mut ProfileList,
+    email: &str,
+) -> Result<LogoutResult, String> {
+    let account = accounts
+        .get(email)
+        .cloned()
+        .ok_or_else(|| format!("로그인된 계정이 아닙니다: {}", email))?;
+    let revoke_url = &profiles
+        .get(&account.profile_id)
+        .unwrap_or_else(|| profiles.active())
+        .config
+        .revoke_url;
+    let errors = revoke_tokens(revoke_url, &account.token).await;
+
+    accounts.remove(email)?;
+    profiles.clear_default_account(email);
+
+    Ok(LogoutResult {
+        email: Some(email.to_string()),
+        revoked: errors.is_empty(),
+        errors,
+    })
+}
+
 /// OAuth 인증 URL 생성
 pub fn build_auth_url(
     client_id: &str,
@@ -84,131 +252,240 @@ pub fn build_auth_url(
         GOOGLE_AUTH_URL,
         urlencoding::encode(client_id),
         urlencoding::encode(redirect_url),
-        urlencoding::encode(FCM_SCOPE),
+        urlencoding::encode(&format!("{} {}", IDENTITY_SCOPES, FCM_SCOPE)),
         urlencoding::encode(state),
         urlencoding::encode(code_challenge),
     )
 }
 
-/// OAuth 콜백 서버 시작 및 인증 코드 수신
-pub fn start_oauth_callback_server(expected_state: &str) -> Result<String, String> {
-    let listener =
-        TcpListener::bind("127.0.0.1:8080").map_err(|e| format!("콜백 서버 바인딩 실패: {}", e))?;
-
-    listener
-        .set_nonblocking(false)
-        .map_err(|e| format!("서버 설정 실패: {}", e))?;
-
-    let (tx, rx) = mpsc::channel();
-    let state = expected_state.to_string();
-
-    thread::spawn(move || {
-        if let Ok((mut stream, _)) = listener.accept() {
-            let mut reader = BufReader::new(stream.try_clone().unwrap());
-            let mut request_line = String::new();
-
-            if reader.read_line(&mut request_line).is_ok() {
-                // GET /callback?code=xxx&state=yyy HTTP/1.1
-                if let Some(params) = request_line.split('?').nth(1) {
-                    let params: Vec<&str> = params
-                        .split_whitespace()
-                        .next()
-                        .unwrap_or("")
-                        .split('&')
-                        .collect();
-
-                    let mut code = None;
-                    let mut received_state = None;
-                    let mut error = None;
-
-                    for param in params {
-                        let kv: Vec<&str> = param.split('=').collect();
-                        if kv.len() == 2 {
-                            match kv[0] {
-                                "code" => code = Some(kv[1].to_string()),
-                                "state" => received_state = Some(kv[1].to_string()),
-                                "error" => error = Some(kv[1].to_string()),
-                                _ => {}
-                            }
-                        }
-                    }
-
-                    let (response_body, result) = if let Some(err) = error {
-                        (
-                            format!(
-                                r#"<html><head><meta charset="utf-8"></head><body style="font-family: Arial; text-align: center; padding: 50px;"><h1 style="color: #f44336;">인증 실패</h1><p>오류: {}</p></body></html>"#,
-                                err
-                            ),
-                            Err(format!("OAuth 오류: {}", err)),
-                        )
-                    } else if received_state.as_ref() != Some(&state) {
-                        (
-                            r#"<html><head><meta charset="utf-8"></head><body style="font-family: Arial; text-align: center; padding: 50px;"><h1 style="color: #f44336;">인증 실패</h1><p>State 검증 실패 (보안 오류)</p></body></html>"#.to_string(),
-                            Err("State 불일치".to_string()),
-                        )
-                    } else if let Some(auth_code) = code {
-                        (
-                            r#"<html><head><meta charset="utf-8"></head><body style="font-family: Arial; text-align: center; padding: 50px;"><h1 style="color: #4CAF50;">인증 성공!</h1><p>이 창을 닫고 애플리케이션으로 돌아가세요.</p></body></html>"#.to_string(),
-                            Ok(auth_code),
-                        )
-                    } else {
-                        (
-                            r#"<html><head><meta charset="utf-8"></head><body style="font-family: Arial; text-align: center; padding: 50px;"><h1 style="color: #f44336;">인증 실패</h1><p>인증 코드가 없습니다.</p></body></html>"#.to_string(),
-                            Err("인증 코드 없음".to_string()),
-                        )
-                    };
-
-                    let response = format!(
-                        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-                        response_body.len(),
-                        response_body
-                    );
-
-                    let _ = stream.write_all(response.as_bytes());
-                    let _ = tx.send(result);
-                }
-            }
-        }
+/// OAuth 인증 진행 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthStage {
+    /// 브라우저를 열고 콜백 대기 중
+    BrowserOpened,
+    /// 콜백으로 인증 코드 수신
+    CallbackReceived,
+    /// 인증 코드를 토큰으로 교환 중
+    Exchanging,
+    /// 인증 완료
+    Done,
+    /// 인증 실패 또는 타임아웃
+    Failed,
+    /// 사용자가 취소
+    Cancelled,
+}
+
+/// OAuth 인증 전체 플로우 실행
+///
+/// 각 단계마다 `on_stage`를 호출하고, `cancel`이 설정되면 콜백 대기를 중단한다.
+pub async fn authenticate<F>(config: &FcmConfig, cancel: Arc<AtomicBool>, on_stage: F) -> AuthResult
+where
+    F: Fn(OAuthStage) + Send + Sync,
+{
+    let result = run_auth_flow(config, &cancel, &on_stage).await;
+
+    on_stage(if result.success {
+        OAuthStage::Done
+    } else if cancel.load(Ordering::SeqCst) {
+        OAuthStage::Cancelled
+    } else {
+        OAuthStage::Failed
     });
 
-    // 5분 타임아웃
-    rx.recv_timeout(std::time::Duration::from_secs(300))
-        .map_err(|_| "인증 타임아웃".to_string())?
+    result
 }
 
-/// OAuth 인증 전체 플로우 실행
-pub async fn authenticate(
-    client_id: &str,
-    redirect_url: &str,
-    exchange_code_url: &str,
-) -> AuthResult {
-    // 1. PKCE 파라미터 생성
+async fn run_auth_flow<F>(config: &FcmConfig, cancel: &Arc<AtomicBool>, on_stage: &F) -> AuthResult
+where
+    F: Fn(OAuthStage) + Send + Sync,
+{
+    // 1. 콜백 리스너 바인딩 (브라우저를 열기 전에 포트를 확보)
+    let listener =
+        match CallbackListener::bind(&config.oauth_redirect_url, config.oauth_ephemeral_port) {
+            Ok(listener) => listener,
+            Err(e) => return AuthResult::failure(e),
+        };
+    let redirect_uri = listener.redirect_uri().to_string();
+
+    // 2. PKCE 파라미터 생성
     let code_verifier = generate_code_verifier();
     let code_challenge = generate_code_challenge(&code_verifier);
     let state = generate_state();
 
-    // 2. 인증 URL 생성
-    let auth_url = build_auth_url(client_id, redirect_url, &state, &code_challenge);
+    // 3. 인증 URL 생성
+    let auth_url = build_auth_url(
+        &config.oauth_client_id,
+        &redirect_uri,
+        &state,
+        &code_challenge,
+    );
 
-    // 3. 브라우저 열기
+    // 4. 브라우저 열기
     if let Err(e) = open::that(&auth_url) {
         return AuthResult::failure(format!("브라우저 열기 실패: {}", e));
     }
+    on_stage(OAuthStage::BrowserOpened);
+
+    // 5. 콜백 수신 (블로킹 대기는 별도 스레드에서)
+    let wait_cancel = Arc::clone(cancel);
+    let waited =
+        tokio::task::spawn_blocking(move || listener.wait_for_code(&state, &wait_cancel)).await;
+    let code = match waited {
+        Ok(Ok(code)) => code,
+        Ok(Err(e)) => return AuthResult::failure(e),
+        Err(e) => return AuthResult::failure(format!("콜백 대기 실패: {}", e)),
+    };
+    on_stage(OAuthStage::CallbackReceived);
+
+    if cancel.load(Ordering::SeqCst) {
+        return AuthResult::failure(CANCELLED_MESSAGE.to_string());
+    }
 
-    // 4. 콜백 서버 시작 및 인증 코드 수신
-    let code = match start_oauth_callback_server(&state) {
-        Ok(code) => code,
-        Err(e) => return AuthResult::failure(e),
+    // 6. 토큰 교환
+    on_stage(OAuthStage::Exchanging);
+    let exchanged = match config.exchange_mode {
+        ExchangeMode::Relay => {
+            exchange_code_via_external_api(
+                &config.exchange_code_url,
+                &code,
+                &redirect_uri,
+                &code_verifier,
+            )
+            .await
+        }
+        ExchangeMode::Direct => {
+            exchange_code_direct(
+                &config.token_url,
+                &config.oauth_client_id,
+                &config.oauth_client_secret,
+                &code,
+                &redirect_uri,
+                &code_verifier,
+            )
+            .await
+        }
     };
 
-    // 5. 토큰 교환
-    match exchange_code_via_external_api(exchange_code_url, &code, redirect_url, &code_verifier)
-        .await
-    {
+    match exchanged {
         Ok(response) => {
             let token = OAuthToken::from_response(response);
-            AuthResult::success(token)
+            AuthResult::success(token).check_domain(&config.allowed_domains)
         }
         Err(e) => AuthResult::failure(format!("토큰 교환 실패: {}", e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcm::mock_server::{unreachable_url, MockServer};
+
+    const EMAIL: &str = "user@example.com";
+
+    /// 취소 URL을 지정한 프로필로 로그인하고 기본 계정으로 지정된 상태
+    fn signed_in(revoke_url: &str) -> (AccountList, ProfileList) {
+        let mut profiles = ProfileList::default();
+        profiles.active_mut().config.revoke_url = revoke_url.to_string();
+        let profile_id = profiles.active().id.clone();
+        profiles
+            .set_default_account(&profile_id, Some(EMAIL.to_string()))
+            .unwrap();
+
+        let mut accounts = AccountList::default();
+        accounts.upsert(
+            EMAIL.to_string(),
+            profile_id,
+            OAuthToken {
+                access_token: "access".to_string(),
+                refresh_token: "refresh".to_string(),
+                id_token: String::new(),
+                token_type: "Bearer".to_string(),
+                expiry: Utc::now() + Duration::hours(1),
+            },
+        );
+        (accounts, profiles)
+    }
+
+    fn assert_signed_out(accounts: &AccountList, profiles: &ProfileList) {
+        assert!(accounts.get(EMAIL).is_none());
+        assert!(accounts.active_email.is_none());
+        assert!(profiles.active().default_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_tokens() {
+        let server = MockServer::start(|_| async { (200, "{}".to_string()) }).await;
+        let (mut accounts, mut profiles) = signed_in(&server.url);
+
+        let result = logout_account(&mut accounts, &mut profiles, EMAIL)
+            .await
+            .unwrap();
+        assert_eq!(result.email.as_deref(), Some(EMAIL));
+        assert!(result.revoked);
+        assert!(result.errors.is_empty());
+        assert_signed_out(&accounts, &profiles);
+
+        let revoked: Vec<String> = server
+            .requests()
+            .iter()
+            .filter_map(|r| r.form("token"))
+            .collect();
+        assert_eq!(revoked, vec!["refresh", "access"]);
+    }
+
+    #[tokio::test]
+    async fn test_logout_already_revoked_token() {
+        let server = MockServer::start(|_| async {
+            let body = serde_json::json!({
+                "error": "invalid_token",
+                "error_description": "Token expired or revoked",
+            });
+            (400, body.to_string())
+        })
+        .await;
+        let (mut accounts, mut profiles) = signed_in(&server.url);
+
+        let result = logout_account(&mut accounts, &mut profiles, EMAIL)
+            .await
+            .unwrap();
+        assert!(result.revoked);
+        assert_signed_out(&accounts, &profiles);
+    }
+
+    #[tokio::test]
+    async fn test_logout_revoke_failure_clears_local_state() {
+        let (mut accounts, mut profiles) = signed_in(&unreachable_url().await);
+
+        let result = logout_account(&mut accounts, &mut profiles, EMAIL)
+            .await
+            .unwrap();
+        assert!(!result.revoked);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].starts_with("refresh_token: "));
+        assert_signed_out(&accounts, &profiles);
+    }
+
+    #[tokio::test]
+    async fn test_logout_unknown_account() {
+        let (mut accounts, mut profiles) = signed_in(&unreachable_url().await);
+        assert!(
+            logout_account(&mut accounts, &mut profiles, "other@example.com")
+                .await
+                .is_err()
+        );
+        assert!(accounts.get(EMAIL).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_not_configured() {
+        let (accounts, _) = signed_in(&unreachable_url().await);
+        let token = &accounts.get(EMAIL).unwrap().token;
+
+        let config = FcmConfig::default();
+        assert_eq!(
+            refresh_access_token(&config, token).await.unwrap_err(),
+            REFRESH_NOT_CONFIGURED_MESSAGE
+        );
+    }
+}
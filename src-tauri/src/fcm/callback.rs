@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// OAuth 콜백 대기 시간 (초)
+const CALLBACK_TIMEOUT_SECS: u64 = 300;
+
+/// 취소 플래그와 대기 시간을 확인하는 주기 (ms)
+const POLL_INTERVAL_MS: u64 = 100;
+
+/// 연결된 클라이언트의 요청 라인 읽기 제한 시간 (초)
+const READ_TIMEOUT_SECS: u64 = 5;
+
+/// 사용자가 인증을 취소했을 때의 에러 메시지
+pub const CANCELLED_MESSAGE: &str = "인증이 취소되었습니다";
+
+const PAGE_STYLE: &str = "font-family: Arial; text-align: center; padding: 50px;";
+
+/// 리다이렉트 URL에서 추출한 콜백 주소
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackAddress {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl CallbackAddress {
+    /// 리다이렉트 URL 파싱 (loopback http 주소만 허용)
+    pub fn parse(redirect_url: &str) -> Result<Self, String> {
+        let url = reqwest::Url::parse(redirect_url)
+            .map_err(|e| format!("리다이렉트 URL 파싱 실패: {}", e))?;
+
+        if url.scheme() != "http" {
+            return Err(format!(
+                "리다이렉트 URL은 http 스킴이어야 합니다: {}",
+                redirect_url
+            ));
+        }
+
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("리다이렉트 URL에 호스트가 없습니다: {}", redirect_url))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+
+        if !matches!(host.as_str(), "localhost" | "127.0.0.1" | "::1") {
+            return Err(format!(
+                "리다이렉트 URL은 loopback 주소여야 합니다 (localhost, 127.0.0.1, [::1]): {}",
+                host
+            ));
+        }
+
+        Ok(Self {
+            host,
+            port: url.port_or_known_default().unwrap_or(80),
+            path: url.path().to_string(),
+        })
+    }
+
+    /// 바인딩할 소켓 주소 (localhost는 IPv4 loopback으로 바인딩)
+    fn bind_addr(&self, port: u16) -> String {
+        match self.host.as_str() {
+            "localhost" | "127.0.0.1" => format!("127.0.0.1:{}", port),
+            _ => format!("[::1]:{}", port),
+        }
+    }
+}
+
+/// OAuth 콜백 리스너
+pub struct CallbackListener {
+    listener: TcpListener,
+    path: String,
+    redirect_uri: String,
+}
+
+impl CallbackListener {
+    /// 리다이렉트 URL의 호스트/포트에 바인딩
+    ///
+    /// `ephemeral_port`가 true이면 OS가 할당한 빈 포트를 사용하고 리다이렉트 URI의 포트를 바꾼다.
+    pub fn bind(redirect_url: &str, ephemeral_port: bool) -> Result<Self, String> {
+        let address = CallbackAddress::parse(redirect_url)?;
+        let port = if ephemeral_port { 0 } else { address.port };
+
+        let listener = TcpListener::bind(address.bind_addr(port))
+            .map_err(|e| format!("콜백 서버 바인딩 실패 ({}): {}", address.bind_addr(port), e))?;
+
+        let redirect_uri = if ephemeral_port {
+            let bound_port = listener
+                .local_addr()
+                .map_err(|e| format!("콜백 서버 주소 확인 실패: {}", e))?
+                .port();
+            let mut url = reqwest::Url::parse(redirect_url)
+                .map_err(|e| format!("리다이렉트 URL 파싱 실패: {}", e))?;
+            url.set_port(Some(bound_port))
+                .map_err(|_| "리다이렉트 URL 포트 변경 실패".to_string())?;
+            url.to_string()
+        } else {
+            redirect_url.to_string()
+        };
+
+        Ok(Self {
+            listener,
+            path: address.path,
+            redirect_uri,
+        })
+    }
+
+    /// 실제로 사용할 리다이렉트 URI
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// 콜백 경로로 들어오는 요청을 기다려 인증 코드 반환
+    ///
+    /// 다른 경로(favicon 등)로 들어온 요청은 404로, state가 일치하지 않는 요청은 에러 페이지로 응답하고 계속 기다린다.
+    /// `cancel`이 설정되거나 대기 시간이 지나면 중단하며, 반환 시 리스너가 닫혀 포트가 해제된다.
+    pub fn wait_for_code(
+        self,
+        expected_state: &str,
+        cancel: &AtomicBool,
+    ) -> Result<String, String> {
+        self.listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("콜백 서버 설정 실패: {}", e))?;
+
+        let deadline = Instant::now() + Duration::from_secs(CALLBACK_TIMEOUT_SECS);
+        loop {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(CANCELLED_MESSAGE.to_string());
+            }
+            if Instant::now() >= deadline {
+                return Err("인증 타임아웃".to_string());
+            }
+
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    let _ = stream.set_nonblocking(false);
+                    let _ = stream.set_read_timeout(Some(Duration::from_secs(READ_TIMEOUT_SECS)));
+                    if let Some(result) = handle_connection(stream, &self.path, expected_state) {
+                        return result;
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+                }
+                Err(_) => thread::sleep(Duration::from_millis(POLL_INTERVAL_MS)),
+            }
+        }
+    }
+}
+
+/// 요청 라인에서 경로와 쿼리 파라미터 추출
+///
+/// 예: `GET /callback?code=xxx&state=yyy HTTP/1.1`
+fn parse_request_line(request_line: &str) -> Option<(String, HashMap<String, String>)> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let target = parts.next()?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params = query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .map(|(k, v)| {
+            let value = urlencoding::decode(v)
+                .map(|d| d.into_owned())
+                .unwrap_or_else(|_| v.to_string());
+            (k.to_string(), value)
+        })
+        .collect();
+
+    Some((path.to_string(), params))
+}
+
+/// 콜백 요청 처리 (콜백 경로가 아니거나 state가 일치하지 않으면 None)
+fn handle_connection(
+    mut stream: TcpStream,
+    expected_path: &str,
+    expected_state: &str,
+) -> Option<Result<String, String>> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+
+    let parsed = parse_request_line(&request_line);
+    let Some((_, params)) = parsed.filter(|(path, _)| path == expected_path) else {
+        write_response(&mut stream, "404 Not Found", "");
+        return None;
+    };
+
+    // 이번 인증 요청이 아닌 콜백은 인증을 끝내지 않음
+    if params.get("state").map(String::as_str) != Some(expected_state) {
+        write_response(
+            &mut stream,
+            "400 Bad Request",
+            &failure_page("State 검증 실패 (보안 오류)"),
+        );
+        return None;
+    }
+
+    let (response_body, result) = if let Some(err) = params.get("error") {
+        (
+            failure_page(&format!("오류: {}", err)),
+            Err(format!("OAuth 오류: {}", err)),
+        )
+    } else if let Some(auth_code) = params.get("code") {
+        (
+            format!(
+                r#"<html><head><meta charset="utf-8"></head><body style="{}"><h1 style="color: #4CAF50;">인증 성공!</h1><p>이 창을 닫고 애플리케이션으로 돌아가세요.</p></body></html>"#,
+                PAGE_STYLE
+            ),
+            Ok(auth_code.clone()),
+        )
+    } else {
+        (
+            failure_page("인증 코드가 없습니다."),
+            Err("인증 코드 없음".to_string()),
+        )
+    };
+
+    write_response(&mut stream, "200 OK", &response_body);
+    Some(result)
+}
+
+fn failure_page(message: &str) -> String {
+    format!(
+        r#"<html><head><meta charset="utf-8"></head><body style="{}"><h1 style="color: #f44336;">인증 실패</h1><p>{}</p></body></html>"#,
+        PAGE_STYLE,
+        escape_html(message)
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// 콜백 서버에 GET 요청을 보내고 응답 전체를 반환
+    fn get(addr: std::net::SocketAddr, target: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_parse_callback_address() {
+        let address = CallbackAddress::parse("http://localhost:8080/callback").unwrap();
+        assert_eq!(address.host, "localhost");
+        assert_eq!(address.port, 8080);
+        assert_eq!(address.path, "/callback");
+
+        let address = CallbackAddress::parse("http://127.0.0.1/oauth2").unwrap();
+        assert_eq!(address.port, 80);
+        assert_eq!(address.path, "/oauth2");
+
+        assert!(CallbackAddress::parse("https://localhost:8080/callback").is_err());
+        assert!(CallbackAddress::parse("http://example.com/callback").is_err());
+    }
+
+    #[test]
+    fn test_parse_request_line() {
+        let (path, params) =
+            parse_request_line("GET /callback?code=4%2F0Ab&state=xyz HTTP/1.1\r\n").unwrap();
+        assert_eq!(path, "/callback");
+        assert_eq!(params["code"], "4/0Ab");
+        assert_eq!(params["state"], "xyz");
+
+        let (path, params) = parse_request_line("GET /favicon.ico HTTP/1.1").unwrap();
+        assert_eq!(path, "/favicon.ico");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_releases_port() {
+        let listener = CallbackListener::bind("http://127.0.0.1:8080/callback", true).unwrap();
+        let addr = listener.listener.local_addr().unwrap();
+
+        let cancel = AtomicBool::new(true);
+        assert_eq!(
+            listener.wait_for_code("state", &cancel).unwrap_err(),
+            CANCELLED_MESSAGE
+        );
+        assert!(TcpListener::bind(addr).is_ok());
+    }
+
+    #[test]
+    fn test_ephemeral_port_rewrites_redirect_uri() {
+        let listener = CallbackListener::bind("http://127.0.0.1:8080/callback", true).unwrap();
+        let port = listener.listener.local_addr().unwrap().port();
+        assert_eq!(
+            listener.redirect_uri(),
+            format!("http://127.0.0.1:{}/callback", port)
+        );
+    }
+
+    #[test]
+    fn test_state_mismatch_keeps_waiting() {
+        let listener = CallbackListener::bind("http://127.0.0.1:8080/callback", true).unwrap();
+        let addr = listener.listener.local_addr().unwrap();
+        let waiter = thread::spawn(move || {
+            let cancel = AtomicBool::new(false);
+            listener.wait_for_code("expected", &cancel)
+        });
+
+        let response = get(addr, "/callback?code=stolen&state=other");
+        assert!(response.starts_with("HTTP/1.1 400"));
+        assert!(response.contains("State 검증 실패"));
+
+        let response = get(addr, "/callback?error=access_denied");
+        assert!(response.starts_with("HTTP/1.1 400"));
+
+        let response = get(addr, "/callback?code=4%2F0Ab&state=expected");
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert_eq!(waiter.join().unwrap(), Ok("4/0Ab".to_string()));
+    }
+
+    #[test]
+    fn test_error_with_matching_state() {
+        let listener = CallbackListener::bind("http://127.0.0.1:8080/callback", true).unwrap();
+        let addr = listener.listener.local_addr().unwrap();
+        let waiter = thread::spawn(move || {
+            let cancel = AtomicBool::new(false);
+            listener.wait_for_code("expected", &cancel)
+        });
+
+        let response = get(
+            addr,
+            "/callback?error=%3Cscript%3Ealert(1)%3C%2Fscript%3E&state=expected",
+        );
+        assert!(response.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!response.contains("<script>"));
+        assert!(waiter.join().unwrap().is_err());
+    }
+}
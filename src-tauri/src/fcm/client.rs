@@ -1,103 +1,319 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::{self, JoinSet};
 
-use super::message::{MessageType, SendRequest, SendResult};
+use super::android::{self, AndroidConfig, AndroidSettings};
+use super::apns::{self, ApnsConfig, ApnsSettings};
+use super::condition::parse_condition;
+use super::config::RetryPolicy;
+use super::error::FcmSendError;
+use super::lint::PayloadSizes;
+use super::message::{MessageType, SendMode, SendRequest, SendResult, TokenResult};
+use super::raw::validate_raw_message;
+use super::validate::{
+    validate_analytics_label, validate_data, validate_https_url, validate_topic_name,
+};
+use super::webpush::WebpushConfig;
 use super::OAuthToken;
 
 /// FCM HTTP v1 API 엔드포인트
-fn fcm_endpoint(project_id: &str) -> String {
+pub(crate) fn fcm_endpoint(project_id: &str) -> String {
     format!(
         "https://fcm.googleapis.com/v1/projects/{}/messages:send",
         project_id
     )
 }
 
+/// 멀티캐스트 한 번에 보낼 수 있는 최대 토큰 수
+pub(crate) const MAX_MULTICAST_TOKENS: usize = 500;
+
+/// 멀티캐스트 동시 요청 수
+const MULTICAST_CONCURRENCY: usize = 10;
+
+/// 토큰별 발송 결과 (시도 횟수, 성공 시 Message ID)
+type SendOutcome = (u32, Result<Option<String>, FcmSendError>);
+
+/// 멀티캐스트 작업 결과를 입력 순서대로 수집
+///
+/// 패닉 등으로 끝나지 못한 작업은 해당 토큰의 실패로 기록하고 나머지 결과는 계속 수집한다.
+async fn join_in_order(
+    mut tasks: JoinSet<SendOutcome>,
+    indices: &HashMap<task::Id, usize>,
+) -> Vec<SendOutcome> {
+    let mut outcomes: Vec<Option<SendOutcome>> = (0..indices.len()).map(|_| None).collect();
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (id, outcome) = match joined {
+            Ok((id, outcome)) => (id, outcome),
+            Err(e) => (
+                e.id(),
+                (
+                    0,
+                    Err(FcmSendError::unknown(format!(
+                        "멀티캐스트 작업 실패: {}",
+                        e
+                    ))),
+                ),
+            ),
+        };
+        if let Some(&index) = indices.get(&id) {
+            outcomes[index] = Some(outcome);
+        }
+    }
+
+    outcomes
+        .into_iter()
+        .map(|outcome| {
+            outcome.unwrap_or_else(|| {
+                (
+                    0,
+                    Err(FcmSendError::unknown(
+                        "멀티캐스트 작업 결과가 없습니다".to_string(),
+                    )),
+                )
+            })
+        })
+        .collect()
+}
+
+/// 멀티캐스트 발송 대상 토큰 (공백 제거, 빈 값과 중복 제외, 입력 순서 유지)
+pub(crate) fn multicast_tokens(tokens: &[String]) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for token in tokens.iter().map(|t| t.trim()) {
+        if !token.is_empty() && !unique.iter().any(|t| t == token) {
+            unique.push(token.to_string());
+        }
+    }
+    unique
+}
+
 /// FCM API 요청 페이로드
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct FcmApiRequest {
+    /// true이면 실제 발송 없이 검증만 수행
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    validate_only: bool,
     message: FcmApiMessage,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct FcmApiMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     topic: Option<String>,
-    notification: FcmNotification,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notification: Option<FcmNotification>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    data: BTreeMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     android: Option<AndroidConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     apns: Option<ApnsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    webpush: Option<WebpushConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fcm_options: Option<FcmOptions>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct FcmNotification {
     title: String,
     body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
 }
 
-/// Android 설정 (FCM API용)
-#[derive(Debug, Serialize)]
-struct AndroidConfig {
-    priority: String,
-    notification: AndroidNotification,
+#[derive(Debug, Clone, Serialize)]
+struct FcmOptions {
+    analytics_label: String,
 }
 
-#[derive(Debug, Serialize)]
-struct AndroidNotification {
-    channel_id: String,
+/// FCM API 응답
+#[derive(Debug, Deserialize)]
+struct FcmApiResponse {
+    name: String,
 }
 
-/// APNs 설정 (FCM API용)
-#[derive(Debug, Serialize)]
-struct ApnsConfig {
-    headers: ApnsHeaders,
-    payload: ApnsPayload,
-}
+/// 발송 요청으로부터 FCM API 메시지 생성 (대상 필드는 호출자가 채움)
+fn build_message(request: &SendRequest) -> Result<FcmApiMessage, String> {
+    if let Some(label) = &request.analytics_label {
+        validate_analytics_label(label).map_err(|e| format!("analyticsLabel: {}", e))?;
+    }
+    let fcm_options = request
+        .analytics_label
+        .clone()
+        .map(|analytics_label| FcmOptions { analytics_label });
 
-#[derive(Debug, Serialize)]
-struct ApnsHeaders {
-    #[serde(rename = "apns-priority")]
-    apns_priority: String,
+    match request.send_mode {
+        SendMode::Notification => {
+            let message = request
+                .message
+                .as_ref()
+                .ok_or_else(|| "알림 제목/내용이 없습니다".to_string())?;
+            if let Some(image) = &message.image {
+                validate_https_url(image).map_err(|e| format!("notification.image: {}", e))?;
+            }
+
+            Ok(FcmApiMessage {
+                token: None,
+                topic: None,
+                condition: None,
+                notification: Some(FcmNotification {
+                    title: message.title.clone(),
+                    body: message.body.clone(),
+                    image: message.image.clone(),
+                }),
+                data: request.data.clone(),
+                android: request
+                    .android
+                    .as_ref()
+                    .map(AndroidSettings::to_config)
+                    .transpose()?,
+                apns: request
+                    .apns
+                    .as_ref()
+                    .map(ApnsSettings::to_config)
+                    .transpose()?,
+                webpush: request
+                    .webpush
+                    .as_ref()
+                    .map(|webpush| webpush.to_config(true))
+                    .transpose()?,
+                fcm_options,
+            })
+        }
+        // 데이터 전용 메시지: notification 블록을 생략하고 백그라운드 전달용 헤더 설정
+        SendMode::DataOnly => Ok(FcmApiMessage {
+            token: None,
+            topic: None,
+            condition: None,
+            notification: None,
+            data: request.data.clone(),
+            android: Some(android::data_only_config(request.android.as_ref())?),
+            apns: Some(apns::data_only_config(request.apns.as_ref())?),
+            webpush: request
+                .webpush
+                .as_ref()
+                .map(|webpush| webpush.to_config(false))
+                .transpose()?,
+            fcm_options,
+        }),
+    }
 }
 
-#[derive(Debug, Serialize)]
-struct ApnsPayload {
-    aps: ApnsAps,
+/// 발송 요청의 FCM API 요청 본문 (대상 포함, 멀티캐스트는 단일 본문으로 표현할 수 없음)
+pub(crate) fn request_body(request: &SendRequest) -> Result<serde_json::Value, String> {
+    let mut message = build_message(request)?;
+    match request.message_type {
+        MessageType::Single => message.token = request.token.clone(),
+        MessageType::Topic => message.topic = request.topic.clone(),
+        MessageType::Condition => message.condition = request.condition.clone(),
+        MessageType::Multicast => {
+            return Err("멀티캐스트 요청은 단일 메시지로 변환할 수 없습니다".to_string())
+        }
+    }
+    serde_json::to_value(FcmApiRequest {
+        validate_only: request.validate_only,
+        message,
+    })
+    .map_err(|e| format!("직렬화 실패: {}", e))
 }
 
-#[derive(Debug, Serialize)]
-struct ApnsAps {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    sound: Option<String>,
+/// 직렬화된 메시지와 플랫폼별 전달 페이로드 크기 추정 (대상 필드 제외)
+pub(crate) fn payload_sizes(request: &SendRequest) -> Result<PayloadSizes, String> {
+    let message = build_message(request)?;
+    let size = |value: serde_json::Value| serde_json::to_vec(&value).map_or(0, |v| v.len() as u32);
+    let notification = message
+        .notification
+        .as_ref()
+        .map(|n| (n.title.as_str(), n.body.as_str()));
+
+    Ok(PayloadSizes {
+        message: size(serde_json::json!(message)),
+        android: size(serde_json::json!({
+            "notification": message.notification,
+            "data": message.data,
+            "android": message.android,
+        })),
+        apns: message
+            .apns
+            .clone()
+            .unwrap_or_default()
+            .estimated_size(notification, &message.data) as u32,
+        webpush: size(serde_json::json!({
+            "notification": message.notification,
+            "data": message.data,
+            "webpush": message.webpush,
+        })),
+    })
 }
 
-/// FCM API 응답
-#[derive(Debug, Deserialize)]
-struct FcmApiResponse {
-    name: String,
+/// 발송은 가능하지만 확인이 필요한 사항
+fn message_warnings(message: &FcmApiMessage) -> Vec<String> {
+    let notification = message
+        .notification
+        .as_ref()
+        .map(|n| (n.title.as_str(), n.body.as_str()));
+
+    message
+        .apns
+        .iter()
+        .filter_map(|apns| apns.size_warning(notification, &message.data))
+        .collect()
 }
 
-/// FCM API 에러 응답
-#[derive(Debug, Deserialize)]
-struct FcmApiError {
-    error: FcmApiErrorDetail,
+/// 재시도 전 대기 시간 계산
+///
+/// Retry-After가 있으면 그 값을 따르고, 없으면 지수 백오프에 jitter를 적용한다.
+/// Retry-After가 정책의 최대값을 넘으면 None (재시도하지 않음).
+fn retry_delay(
+    policy: &RetryPolicy,
+    attempt: u32,
+    retry_after_secs: Option<u32>,
+) -> Option<Duration> {
+    let max_backoff = Duration::from_millis(policy.max_backoff_ms as u64);
+
+    if let Some(secs) = retry_after_secs {
+        let delay = Duration::from_secs(secs as u64);
+        return (delay <= max_backoff).then_some(delay);
+    }
+
+    let exponent = attempt.saturating_sub(1).min(16);
+    let backoff = Duration::from_millis(policy.initial_backoff_ms as u64)
+        .saturating_mul(1 << exponent)
+        .min(max_backoff);
+    // 대기 시간의 50~100% 범위에서 무작위 선택
+    let jitter = rand::thread_rng().gen_range(0.5..=1.0);
+    Some(backoff.mul_f64(jitter))
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct FcmApiErrorDetail {
-    message: String,
-    #[serde(default)]
-    status: String,
+/// Retry-After 헤더 파싱 (초 단위 또는 HTTP-date)
+///
+/// HTTP-date는 `now`부터 남은 초로 변환하며, 이미 지난 시각이면 0.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<u32> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u32>() {
+        return Some(secs);
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (date.with_timezone(&Utc) - now).num_seconds().max(0);
+    Some(u32::try_from(secs).unwrap_or(u32::MAX))
 }
 
 /// FCM 클라이언트
+#[derive(Clone)]
 pub struct FcmClient {
     http_client: reqwest::Client,
-    project_id: String,
+    endpoint: String,
     access_token: String,
+    retry_policy: RetryPolicy,
 }
 
 impl FcmClient {
@@ -110,17 +326,58 @@ impl FcmClient {
 
         Ok(Self {
             http_client,
-            project_id: project_id.to_string(),
+            endpoint: fcm_endpoint(project_id),
             access_token: token.access_token.clone(),
+            retry_policy: RetryPolicy::default(),
         })
     }
 
+    /// 재시도 정책 설정
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// FCM API 엔드포인트 변경 (테스트용 로컬 서버)
+    #[cfg(test)]
+    fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
     /// 메시지 발송
     pub async fn send(&self, request: SendRequest) -> SendResult {
-        match request.message_type {
+        if let Err(e) = validate_data(&request.data) {
+            return SendResult::failure(e);
+        }
+
+        let warnings = build_message(&request)
+            .map(|message| message_warnings(&message))
+            .unwrap_or_default();
+
+        let result = match request.message_type {
             MessageType::Single => self.send_to_token(&request).await,
             MessageType::Topic => self.send_to_topic(&request).await,
+            MessageType::Condition => self.send_to_condition(&request).await,
+            MessageType::Multicast => self.send_multicast(&request).await,
+        };
+        result.with_warnings(warnings)
+    }
+
+    /// raw 요청 본문(`{"message": {...}}`)을 검증 후 그대로 발송
+    pub async fn send_raw(&self, document: &serde_json::Value) -> SendResult {
+        let issues = validate_raw_message(document);
+        if !issues.is_empty() {
+            let details: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+            return SendResult::failure(details.join("\n"));
         }
+
+        let validate_only = document
+            .get("validate_only")
+            .or_else(|| document.get("validateOnly"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        self.send_payload(document, validate_only).await
     }
 
     /// 단일 디바이스에 발송
@@ -130,34 +387,17 @@ impl FcmClient {
             _ => return SendResult::failure("디바이스 토큰이 없습니다".to_string()),
         };
 
-        let payload = FcmApiRequest {
-            message: FcmApiMessage {
-                token: Some(token.clone()),
-                topic: None,
-                notification: FcmNotification {
-                    title: request.message.title.clone(),
-                    body: request.message.body.clone(),
-                },
-                android: request.android.as_ref().map(|a| AndroidConfig {
-                    priority: a.priority.clone(),
-                    notification: AndroidNotification {
-                        channel_id: a.channel_id.clone(),
-                    },
-                }),
-                apns: request.apns.as_ref().map(|a| ApnsConfig {
-                    headers: ApnsHeaders {
-                        apns_priority: a.priority.clone(),
-                    },
-                    payload: ApnsPayload {
-                        aps: ApnsAps {
-                            sound: a.sound.clone(),
-                        },
-                    },
-                }),
-            },
+        let mut message = match build_message(request) {
+            Ok(m) => m,
+            Err(e) => return SendResult::failure(e),
         };
+        message.token = Some(token.clone());
 
-        self.send_single_message(payload).await
+        self.send_single_message(FcmApiRequest {
+            validate_only: request.validate_only,
+            message,
+        })
+        .await
     }
 
     /// 토픽에 발송
@@ -166,72 +406,654 @@ impl FcmClient {
             Some(t) if !t.is_empty() => t,
             _ => return SendResult::failure("토픽 이름이 없습니다".to_string()),
         };
+        if let Err(e) = validate_topic_name(topic) {
+            return SendResult::failure(e);
+        }
 
-        let payload = FcmApiRequest {
-            message: FcmApiMessage {
-                token: None,
-                topic: Some(topic.clone()),
-                notification: FcmNotification {
-                    title: request.message.title.clone(),
-                    body: request.message.body.clone(),
-                },
-                android: request.android.as_ref().map(|a| AndroidConfig {
-                    priority: a.priority.clone(),
-                    notification: AndroidNotification {
-                        channel_id: a.channel_id.clone(),
-                    },
-                }),
-                apns: request.apns.as_ref().map(|a| ApnsConfig {
-                    headers: ApnsHeaders {
-                        apns_priority: a.priority.clone(),
-                    },
-                    payload: ApnsPayload {
-                        aps: ApnsAps {
-                            sound: a.sound.clone(),
-                        },
-                    },
-                }),
-            },
+        let mut message = match build_message(request) {
+            Ok(m) => m,
+            Err(e) => return SendResult::failure(e),
+        };
+        message.topic = Some(topic.clone());
+
+        self.send_single_message(FcmApiRequest {
+            validate_only: request.validate_only,
+            message,
+        })
+        .await
+    }
+
+    /// 토픽 조건식에 발송
+    async fn send_to_condition(&self, request: &SendRequest) -> SendResult {
+        let condition = match &request.condition {
+            Some(c) if !c.trim().is_empty() => c.trim(),
+            _ => return SendResult::failure("토픽 조건식이 없습니다".to_string()),
+        };
+        if let Err(e) = parse_condition(condition) {
+            return SendResult::failure(format!("조건식 오류: {}", e));
+        }
+
+        let mut message = match build_message(request) {
+            Ok(m) => m,
+            Err(e) => return SendResult::failure(e),
+        };
+        message.condition = Some(condition.to_string());
+
+        self.send_single_message(FcmApiRequest {
+            validate_only: request.validate_only,
+            message,
+        })
+        .await
+    }
+
+    /// 여러 디바이스 토큰에 발송 (토큰별 결과 반환)
+    async fn send_multicast(&self, request: &SendRequest) -> SendResult {
+        let tokens = multicast_tokens(&request.tokens);
+
+        if tokens.is_empty() {
+            return SendResult::failure("디바이스 토큰이 없습니다".to_string());
+        }
+        if tokens.len() > MAX_MULTICAST_TOKENS {
+            return SendResult::failure(format!(
+                "멀티캐스트는 최대 {}개의 토큰까지 가능합니다 (현재 {}개)",
+                MAX_MULTICAST_TOKENS,
+                tokens.len()
+            ));
+        }
+
+        let message = match build_message(request) {
+            Ok(m) => m,
+            Err(e) => return SendResult::failure(e),
         };
 
-        self.send_single_message(payload).await
+        let semaphore = Arc::new(Semaphore::new(MULTICAST_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        let mut indices = HashMap::with_capacity(tokens.len());
+
+        for (index, token) in tokens.iter().enumerate() {
+            let client = self.clone();
+            let semaphore = semaphore.clone();
+            let mut message = message.clone();
+            message.token = Some(token.clone());
+            let payload = FcmApiRequest {
+                validate_only: request.validate_only,
+                message,
+            };
+
+            let handle = tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                client.post_message_with_retry(&payload).await
+            });
+            indices.insert(handle.id(), index);
+        }
+
+        let results: Vec<TokenResult> = join_in_order(tasks, &indices)
+            .await
+            .into_iter()
+            .zip(tokens)
+            .map(|((attempts, outcome), token)| {
+                match outcome {
+                    Ok(message_id) => TokenResult::success(token, message_id),
+                    Err(e) => TokenResult::failure(token, e),
+                }
+                .with_attempts(attempts)
+            })
+            .collect();
+
+        SendResult::multicast(results, request.validate_only)
     }
 
     /// 단일 메시지 발송 (내부 함수)
     async fn send_single_message(&self, payload: FcmApiRequest) -> SendResult {
-        let endpoint = fcm_endpoint(&self.project_id);
+        self.send_payload(&payload, payload.validate_only).await
+    }
+
+    /// 요청 본문 발송 후 결과 변환
+    async fn send_payload<T: Serialize>(&self, payload: &T, validate_only: bool) -> SendResult {
+        let (attempts, outcome) = self.post_message_with_retry(payload).await;
+
+        let result = match outcome {
+            Ok(Some(name)) if validate_only => SendResult::validated(format!(
+                "검증 성공 (실제 발송되지 않음) Message ID: {}",
+                name
+            )),
+            Ok(None) if validate_only => {
+                SendResult::validated("검증 성공 (실제 발송되지 않음)".to_string())
+            }
+            Ok(Some(name)) => SendResult::success(format!("발송 성공! Message ID: {}", name)),
+            Ok(None) => SendResult::success("발송 성공".to_string()),
+            Err(e) => SendResult::fcm_failure(e),
+        };
+        result.with_attempts(attempts)
+    }
+
+    /// 재시도 정책에 따라 FCM API 호출 (시도 횟수와 결과 반환)
+    async fn post_message_with_retry<T: Serialize>(
+        &self,
+        payload: &T,
+    ) -> (u32, Result<Option<String>, FcmSendError>) {
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        let mut attempt = 1;
 
-        let response = match self
+        loop {
+            let outcome = self.post_message(payload).await;
+            let Err(ref e) = outcome else {
+                return (attempt, outcome);
+            };
+            if attempt >= max_attempts || !e.code.is_retryable() {
+                return (attempt, outcome);
+            }
+            let Some(delay) = retry_delay(&self.retry_policy, attempt, e.retry_after_secs) else {
+                // 서버가 요구한 대기 시간이 너무 길면 포기 사유를 실패 상세에 남김
+                let mut error = e.clone();
+                error.message = format!(
+                    "{} (Retry-After {}초가 최대 대기 시간 {}ms보다 길어 재시도하지 않음)",
+                    error.message,
+                    error.retry_after_secs.unwrap_or_default(),
+                    self.retry_policy.max_backoff_ms
+                );
+                return (attempt, Err(error));
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// FCM API 호출 (성공 시 Message ID 반환)
+    async fn post_message<T: Serialize>(
+        &self,
+        payload: &T,
+    ) -> Result<Option<String>, FcmSendError> {
+        let response = self
             .http_client
-            .post(&endpoint)
+            .post(&self.endpoint)
             .bearer_auth(&self.access_token)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
-        {
-            Ok(r) => r,
-            Err(e) => return SendResult::failure(format!("HTTP 요청 실패: {}", e)),
-        };
+            .map_err(|e| FcmSendError::network(format!("HTTP 요청 실패: {}", e)))?;
 
         let status = response.status();
-        let body = match response.text().await {
-            Ok(b) => b,
-            Err(e) => return SendResult::failure(format!("응답 읽기 실패: {}", e)),
-        };
+        let retry_after_secs = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_retry_after(v, Utc::now()));
+        let body = response
+            .text()
+            .await
+            .map_err(|e| FcmSendError::network(format!("응답 읽기 실패: {}", e)))?;
 
         if status.is_success() {
-            if let Ok(resp) = serde_json::from_str::<FcmApiResponse>(&body) {
-                SendResult::success(format!("발송 성공! Message ID: {}", resp.name))
-            } else {
-                SendResult::success("발송 성공".to_string())
-            }
+            Ok(serde_json::from_str::<FcmApiResponse>(&body)
+                .ok()
+                .map(|resp| resp.name))
         } else {
-            let error_msg = if let Ok(err) = serde_json::from_str::<FcmApiError>(&body) {
-                err.error.message
-            } else {
-                body
-            };
-            SendResult::failure(format!("발송 실패 ({}): {}", status.as_u16(), error_msg))
+            let mut error = FcmSendError::from_response(status.as_u16(), &body);
+            error.retry_after_secs = retry_after_secs;
+            Err(error)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcm::error::FcmErrorCode;
+    use crate::fcm::message::{FcmMessage, SendStatus};
+    use crate::fcm::mock_server::{MockServer, RecordedRequest};
+
+    fn request(send_mode: SendMode) -> SendRequest {
+        SendRequest {
+            message_type: MessageType::Topic,
+            send_mode,
+            message: Some(FcmMessage {
+                title: "title".to_string(),
+                body: "body".to_string(),
+                image: None,
+            }),
+            token: None,
+            topic: Some("news".to_string()),
+            condition: None,
+            tokens: Vec::new(),
+            android: None,
+            apns: None,
+            webpush: None,
+            analytics_label: None,
+            template: None,
+            validate_only: false,
+            data: BTreeMap::from([("deeplink".to_string(), "app://home".to_string())]),
+        }
+    }
+
+    /// 로컬 서버로 요청을 보내는 클라이언트 (재시도 없음)
+    fn mock_client(server: &MockServer) -> FcmClient {
+        let token = OAuthToken {
+            access_token: "access".to_string(),
+            refresh_token: String::new(),
+            id_token: String::new(),
+            token_type: "Bearer".to_string(),
+            expiry: chrono::Utc::now() + chrono::Duration::hours(1),
+        };
+        FcmClient::new("project", &token)
+            .unwrap()
+            .with_endpoint(server.url.clone())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+    }
+
+    /// 요청 본문의 message.token
+    fn message_token(request: &RecordedRequest) -> String {
+        request.json()["message"]["token"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    async fn accept_all(request: RecordedRequest) -> (u16, String) {
+        let name = format!("projects/project/messages/{}", message_token(&request));
+        (200, serde_json::json!({ "name": name }).to_string())
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 4000,
+        };
+
+        let first = retry_delay(&policy, 1, None).unwrap();
+        assert!(first >= Duration::from_millis(500) && first <= Duration::from_millis(1000));
+        let capped = retry_delay(&policy, 10, None).unwrap();
+        assert!(capped <= Duration::from_millis(4000));
+
+        assert_eq!(
+            retry_delay(&policy, 1, Some(2)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(retry_delay(&policy, 1, Some(60)), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:27:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("120", now), Some(120));
+        assert_eq!(parse_retry_after(" 5 ", now), Some(5));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(60)
+        );
+        // 이미 지난 시각이면 바로 재시도
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(0)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    /// 503 UNAVAILABLE 응답 본문
+    fn unavailable_body() -> String {
+        serde_json::json!({
+            "error": { "code": 503, "message": "Service unavailable", "status": "UNAVAILABLE" }
+        })
+        .to_string()
+    }
+
+    /// 첫 요청에만 Retry-After와 함께 503으로 응답하는 서버
+    async fn unavailable_once(retry_after: String) -> MockServer {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        MockServer::start_with_headers(move |request: RecordedRequest| {
+            let first = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0;
+            let retry_after = retry_after.clone();
+            async move {
+                if first {
+                    let headers = vec![("Retry-After".to_string(), retry_after)];
+                    (503, headers, unavailable_body())
+                } else {
+                    let (status, body) = accept_all(request).await;
+                    (status, Vec::new(), body)
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_http_date() {
+        // 이미 지난 HTTP-date이므로 바로 재시도 (파싱하지 못하면 5초 백오프)
+        let retry_after = (Utc::now() - chrono::Duration::seconds(10)).to_rfc2822();
+        let server = unavailable_once(retry_after).await;
+        let client = mock_client(&server).with_retry_policy(RetryPolicy {
+            max_attempts: 2,
+            initial_backoff_ms: 5000,
+            max_backoff_ms: 5000,
+        });
+
+        let started = std::time::Instant::now();
+        let result = client.send(request(SendMode::Notification)).await;
+        assert_eq!(result.status, SendStatus::Sent);
+        assert_eq!(result.attempts, 2);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_beyond_max_backoff() {
+        let server = unavailable_once("120".to_string()).await;
+        let client = mock_client(&server).with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+        });
+
+        let result = client.send(request(SendMode::Notification)).await;
+        assert_eq!(result.status, SendStatus::Failed);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(result.error.as_ref().unwrap().retry_after_secs, Some(120));
+        assert!(result.details.contains("Retry-After 120초"));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_notification_message() {
+        let message = build_message(&request(SendMode::Notification)).unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["notification"]["title"], "title");
+        assert_eq!(json["data"]["deeplink"], "app://home");
+        assert!(json.get("apns").is_none());
+        assert!(json.get("fcm_options").is_none());
+    }
+
+    #[test]
+    fn test_image_and_analytics_label() {
+        let mut req = request(SendMode::Notification);
+        req.message.as_mut().unwrap().image = Some("https://example.com/a.png".to_string());
+        req.analytics_label = Some("spring_sale-2024".to_string());
+        let json = serde_json::to_value(build_message(&req).unwrap()).unwrap();
+        assert_eq!(json["notification"]["image"], "https://example.com/a.png");
+        assert_eq!(json["fcm_options"]["analytics_label"], "spring_sale-2024");
+
+        req.analytics_label = Some("spring sale".to_string());
+        assert!(build_message(&req).is_err());
+
+        req.analytics_label = None;
+        req.message.as_mut().unwrap().image = Some("http://example.com/a.png".to_string());
+        assert!(build_message(&req).is_err());
+    }
+
+    #[test]
+    fn test_data_only_message() {
+        let message = build_message(&request(SendMode::DataOnly)).unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+        assert!(json.get("notification").is_none());
+        assert_eq!(json["android"]["priority"], "normal");
+        assert!(json["android"].get("notification").is_none());
+        assert_eq!(json["apns"]["headers"]["apns-priority"], "5");
+        assert_eq!(json["apns"]["headers"]["apns-push-type"], "background");
+        assert_eq!(json["apns"]["payload"]["aps"]["content-available"], 1);
+    }
+
+    #[test]
+    fn test_validate_only_body() {
+        let mut req = request(SendMode::Notification);
+        let json = request_body(&req).unwrap();
+        assert!(json.get("validate_only").is_none());
+
+        req.validate_only = true;
+        let json = request_body(&req).unwrap();
+        assert_eq!(json["validate_only"], true);
+        assert_eq!(json["message"]["topic"], "news");
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_send() {
+        let server = MockServer::start(accept_all).await;
+        let client = mock_client(&server);
+
+        let mut req = request(SendMode::Notification);
+        req.validate_only = true;
+        let result = client.send(req.clone()).await;
+        assert_eq!(result.status, SendStatus::Validated);
+        assert!(result.details.contains("실제 발송되지 않음"));
+
+        req.validate_only = false;
+        let result = client.send(req).await;
+        assert_eq!(result.status, SendStatus::Sent);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].json()["validate_only"], true);
+        assert!(requests[1].json().get("validate_only").is_none());
+        assert_eq!(requests[0].header("authorization"), Some("Bearer access"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_failure() {
+        let server = MockServer::start(|_| async {
+            let body = serde_json::json!({
+                "error": { "code": 400, "message": "Invalid token", "status": "INVALID_ARGUMENT" }
+            });
+            (400, body.to_string())
+        })
+        .await;
+
+        let mut req = request(SendMode::Notification);
+        req.validate_only = true;
+        let result = mock_client(&server).send(req).await;
+        assert_eq!(result.status, SendStatus::Failed);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_data_only_explicit_priority() {
+        let mut req = request(SendMode::DataOnly);
+        req.android = Some(
+            serde_json::from_value(serde_json::json!({
+                "priority": "high",
+                "channelId": "default",
+            }))
+            .unwrap(),
+        );
+        req.apns = Some(serde_json::from_value(serde_json::json!({ "priority": "10" })).unwrap());
+        let json = serde_json::to_value(build_message(&req).unwrap()).unwrap();
+        assert_eq!(json["android"]["priority"], "high");
+        assert!(json["android"].get("notification").is_none());
+        // APNs는 background 푸시에 우선순위 5만 허용
+        assert_eq!(json["apns"]["headers"]["apns-priority"], "5");
+        assert_eq!(json["apns"]["headers"]["apns-push-type"], "background");
+
+        // 우선순위를 비워두면 기본값 사용
+        req.android.as_mut().unwrap().priority = String::new();
+        req.apns.as_mut().unwrap().priority = String::new();
+        let json = serde_json::to_value(build_message(&req).unwrap()).unwrap();
+        assert_eq!(json["android"]["priority"], "normal");
+        assert_eq!(json["apns"]["headers"]["apns-priority"], "5");
+    }
+
+    fn multicast_request(tokens: &[&str]) -> SendRequest {
+        let mut req = request(SendMode::Notification);
+        req.message_type = MessageType::Multicast;
+        req.topic = None;
+        req.tokens = tokens.iter().map(|t| t.to_string()).collect();
+        req
+    }
+
+    #[test]
+    fn test_multicast_tokens() {
+        let tokens: Vec<String> = [" a ", "", "b", "a", "  ", "c", "b"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        assert_eq!(multicast_tokens(&tokens), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_multicast_aggregation() {
+        let results = vec![
+            TokenResult::success("a".to_string(), None).with_attempts(1),
+            TokenResult::success("b".to_string(), None).with_attempts(3),
+        ];
+        let result = SendResult::multicast(results.clone(), false);
+        assert_eq!(result.status, SendStatus::Sent);
+        assert_eq!(result.attempts, 3);
+        assert!(result.details.contains("2개 중 2개 성공"));
+
+        let result = SendResult::multicast(results, true);
+        assert_eq!(result.status, SendStatus::Validated);
+
+        let results = vec![
+            TokenResult::success("a".to_string(), None),
+            TokenResult::failure(
+                "b".to_string(),
+                FcmSendError::network("timeout".to_string()),
+            ),
+        ];
+        let result = SendResult::multicast(results.clone(), false);
+        assert_eq!(result.status, SendStatus::Failed);
+        assert!(result.details.contains("2개 중 1개 성공"));
+        assert_eq!(
+            SendResult::multicast(results, true).status,
+            SendStatus::Failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multicast_dedup() {
+        let server = MockServer::start(accept_all).await;
+        let result = mock_client(&server)
+            .send(multicast_request(&[" a ", "", "b", "a", "  ", "b "]))
+            .await;
+
+        assert_eq!(result.status, SendStatus::Sent);
+        let tokens: Vec<&str> = result
+            .token_results
+            .iter()
+            .map(|r| r.token.as_str())
+            .collect();
+        assert_eq!(tokens, vec!["a", "b"]);
+
+        let mut sent: Vec<String> = server.requests().iter().map(message_token).collect();
+        sent.sort();
+        assert_eq!(sent, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_multicast_limit() {
+        let server = MockServer::start(accept_all).await;
+        let client = mock_client(&server);
+
+        let tokens: Vec<String> = (0..=MAX_MULTICAST_TOKENS)
+            .map(|i| format!("token-{}", i))
+            .collect();
+        let mut req = multicast_request(&[]);
+        req.tokens = tokens.clone();
+        let result = client.send(req.clone()).await;
+        assert_eq!(result.status, SendStatus::Failed);
+        assert!(server.requests().is_empty());
+
+        // 중복을 제외하면 한도 이내
+        req.tokens = tokens[..MAX_MULTICAST_TOKENS].to_vec();
+        req.tokens.push(tokens[0].clone());
+        let result = client.send(req).await;
+        assert_eq!(result.status, SendStatus::Sent);
+        assert_eq!(result.token_results.len(), MAX_MULTICAST_TOKENS);
+
+        let result = client.send(multicast_request(&["", " "])).await;
+        assert_eq!(result.status, SendStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_multicast_keeps_input_order() {
+        // 앞쪽 토큰일수록 늦게 응답
+        let server = MockServer::start(|request: RecordedRequest| async move {
+            let token = message_token(&request);
+            let delay = match token.as_str() {
+                "first" => 150,
+                "second" => 75,
+                _ => 0,
+            };
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            accept_all(request).await
+        })
+        .await;
+
+        let result = mock_client(&server)
+            .send(multicast_request(&["first", "second", "third"]))
+            .await;
+        let tokens: Vec<&str> = result
+            .token_results
+            .iter()
+            .map(|r| r.token.as_str())
+            .collect();
+        assert_eq!(tokens, vec!["first", "second", "third"]);
+        assert_eq!(
+            result.token_results[0].message_id.as_deref(),
+            Some("projects/project/messages/first")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multicast_partial_failure() {
+        let server = MockServer::start(|request: RecordedRequest| async move {
+            if message_token(&request) != "stale" {
+                return accept_all(request).await;
+            }
+            let body = serde_json::json!({
+                "error": {
+                    "code": 404,
+                    "message": "Requested entity was not found.",
+                    "status": "NOT_FOUND",
+                    "details": [{
+                        "@type": "type.googleapis.com/google.firebase.fcm.v1.FcmError",
+                        "errorCode": "UNREGISTERED"
+                    }]
+                }
+            });
+            (404, body.to_string())
+        })
+        .await;
+
+        let result = mock_client(&server)
+            .send(multicast_request(&["a", "stale", "b"]))
+            .await;
+        assert_eq!(result.status, SendStatus::Failed);
+        assert!(result.details.contains("3개 중 2개 성공"));
+        assert!(result.token_results[0].success);
+        assert!(!result.token_results[1].success);
+        assert_eq!(
+            result.token_results[1].error_code,
+            Some(FcmErrorCode::Unregistered)
+        );
+        assert!(result.token_results[2].success);
+    }
+
+    #[tokio::test]
+    async fn test_join_in_order_keeps_failed_task() {
+        let mut tasks: JoinSet<SendOutcome> = JoinSet::new();
+        let mut indices = HashMap::new();
+        let handle = tasks.spawn(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            (1, Ok(Some("first".to_string())))
+        });
+        indices.insert(handle.id(), 0);
+        let handle = tasks.spawn(async { panic!("task panicked") });
+        indices.insert(handle.id(), 1);
+        let handle = tasks.spawn(async { (2, Ok(Some("third".to_string()))) });
+        indices.insert(handle.id(), 2);
+
+        let outcomes = join_in_order(tasks, &indices).await;
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0].1.as_ref().unwrap().as_deref(), Some("first"));
+        let error = outcomes[1].1.as_ref().unwrap_err();
+        assert_eq!(error.code, FcmErrorCode::Unknown);
+        assert!(error.message.contains("멀티캐스트 작업 실패"));
+        assert_eq!(outcomes[2].0, 2);
+        assert_eq!(outcomes[2].1.as_ref().unwrap().as_deref(), Some("third"));
+    }
+}
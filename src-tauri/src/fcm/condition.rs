@@ -0,0 +1,290 @@
+use super::validate::validate_topic_name;
+
+/// 조건식 하나에 포함할 수 있는 최대 토픽 수
+const MAX_CONDITION_TOPICS: usize = 5;
+
+/// 괄호와 `!`의 최대 중첩 깊이 (재귀 파서의 스택 오버플로 방지)
+const MAX_CONDITION_DEPTH: usize = 32;
+
+/// 조건식 토큰
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Topic(String),
+    In,
+    Topics,
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+/// 조건식을 토큰 단위로 분리
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.char_indices().peekable();
+
+    while let Some(&(pos, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParen);
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '&' | '|' => {
+                chars.next();
+                match chars.next() {
+                    Some((_, next)) if next == c => {
+                        tokens.push(if c == '&' { Token::And } else { Token::Or });
+                    }
+                    _ => {
+                        return Err(format!(
+                            "{}번째 문자: '{}{}' 연산자가 필요합니다",
+                            pos + 1,
+                            c,
+                            c
+                        ))
+                    }
+                }
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut name = String::new();
+                let mut closed = false;
+                for (_, ch) in chars.by_ref() {
+                    if ch == c {
+                        closed = true;
+                        break;
+                    }
+                    name.push(ch);
+                }
+                if !closed {
+                    return Err(format!("{}번째 문자: 따옴표가 닫히지 않았습니다", pos + 1));
+                }
+                tokens.push(Token::Topic(name));
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if !ch.is_ascii_alphabetic() {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "in" => tokens.push(Token::In),
+                    "topics" => tokens.push(Token::Topics),
+                    _ => {
+                        return Err(format!(
+                            "{}번째 문자: 알 수 없는 키워드 '{}'",
+                            pos + 1,
+                            word
+                        ))
+                    }
+                }
+            }
+            _ => return Err(format!("{}번째 문자: 허용되지 않는 문자 '{}'", pos + 1, c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// 재귀 하강 파서
+///
+/// ```text
+/// or      := and ("||" and)*
+/// and     := unary ("&&" unary)*
+/// unary   := "!" unary | primary
+/// primary := "(" or ")" | TOPIC "in" "topics"
+/// ```
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    topics: Vec<String>,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// 중첩 한 단계 진입 (최대 깊이를 넘으면 에러)
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_CONDITION_DEPTH {
+            return Err(format!(
+                "조건식의 괄호와 '!' 중첩은 최대 {}단계까지 가능합니다",
+                MAX_CONDITION_DEPTH
+            ));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<(), String> {
+        self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            self.parse_and()?;
+        }
+        Ok(())
+    }
+
+    fn parse_and(&mut self) -> Result<(), String> {
+        self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            self.parse_unary()?;
+        }
+        Ok(())
+    }
+
+    fn parse_unary(&mut self) -> Result<(), String> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            self.enter()?;
+            self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(());
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<(), String> {
+        match self.next() {
+            Some(Token::LeftParen) => {
+                self.enter()?;
+                self.parse_or()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Token::RightParen) => Ok(()),
+                    _ => Err("괄호가 닫히지 않았습니다".to_string()),
+                }
+            }
+            Some(Token::Topic(name)) => {
+                validate_topic_name(&name)?;
+                if self.next() != Some(Token::In) || self.next() != Some(Token::Topics) {
+                    return Err(format!("'{}' 다음에 'in topics'가 필요합니다", name));
+                }
+                self.topics.push(name);
+                Ok(())
+            }
+            Some(Token::RightParen) => Err("여는 괄호 없이 닫는 괄호가 있습니다".to_string()),
+            Some(_) => Err("토픽 이름 또는 여는 괄호가 필요합니다".to_string()),
+            None => Err("조건식이 불완전합니다".to_string()),
+        }
+    }
+}
+
+/// 토픽 조건식을 검증하고 포함된 토픽 목록을 반환
+///
+/// 예: `'a' in topics && ('b' in topics || 'c' in topics)`
+pub fn parse_condition(expr: &str) -> Result<Vec<String>, String> {
+    let tokens = tokenize(expr)?;
+    if tokens.is_empty() {
+        return Err("조건식이 비어 있습니다".to_string());
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        topics: Vec::new(),
+        depth: 0,
+    };
+    parser.parse_or()?;
+
+    match parser.peek() {
+        None => {}
+        Some(Token::RightParen) => {
+            return Err("여는 괄호 없이 닫는 괄호가 있습니다".to_string());
+        }
+        Some(_) => return Err("조건식 뒤에 불필요한 토큰이 있습니다".to_string()),
+    }
+
+    if parser.topics.len() > MAX_CONDITION_TOPICS {
+        return Err(format!(
+            "조건식에는 최대 {}개의 토픽만 사용할 수 있습니다 (현재 {}개)",
+            MAX_CONDITION_TOPICS,
+            parser.topics.len()
+        ));
+    }
+
+    Ok(parser.topics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_conditions() {
+        assert_eq!(parse_condition("'a' in topics").unwrap(), vec!["a"]);
+        assert_eq!(
+            parse_condition("'a' in topics && ('b' in topics || 'c' in topics)").unwrap(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            parse_condition("!('dogs' in topics) && \"cats\" in topics").unwrap(),
+            vec!["dogs", "cats"]
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_parentheses() {
+        assert!(parse_condition("('a' in topics").is_err());
+        assert!(parse_condition("'a' in topics)").is_err());
+        assert!(parse_condition("('a' in topics || 'b' in topics))").is_err());
+    }
+
+    #[test]
+    fn test_too_many_topics() {
+        let expr = "'a' in topics || 'b' in topics || 'c' in topics || 'd' in topics || 'e' in topics || 'f' in topics";
+        assert!(parse_condition(expr).is_err());
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        let nested = |depth: usize| {
+            format!(
+                "{}'a' in topics{}",
+                "(!".repeat(depth / 2),
+                ")".repeat(depth / 2)
+            )
+        };
+        assert!(parse_condition(&nested(MAX_CONDITION_DEPTH)).is_ok());
+        assert!(parse_condition(&nested(MAX_CONDITION_DEPTH + 2)).is_err());
+
+        // 스택 오버플로 없이 에러 반환
+        let expr = format!("{}'a' in topics", "!".repeat(100_000));
+        assert!(parse_condition(&expr).unwrap_err().contains("중첩"));
+        let expr = format!("{}'a' in topics", "(".repeat(100_000));
+        assert!(parse_condition(&expr).unwrap_err().contains("중첩"));
+    }
+
+    #[test]
+    fn test_invalid_syntax() {
+        assert!(parse_condition("").is_err());
+        assert!(parse_condition("'a' in topics & 'b' in topics").is_err());
+        assert!(parse_condition("'a' topics").is_err());
+        assert!(parse_condition("'a b' in topics").is_err());
+        assert!(parse_condition("'a' in topics &&").is_err());
+    }
+}
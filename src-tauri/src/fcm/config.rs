@@ -1,30 +1,124 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use super::exchange::{GOOGLE_REVOKE_URL, GOOGLE_TOKEN_URL};
+
+/// 인증 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMode {
+    /// 브라우저 OAuth (PKCE)
+    #[default]
+    OAuth,
+    /// 서비스 계정 JSON 키
+    ServiceAccount,
+}
+
+/// authorization code 교환 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeMode {
+    /// 외부 토큰 교환 API (relay)를 통해 교환
+    #[default]
+    Relay,
+    /// Google 토큰 엔드포인트에 직접 교환
+    Direct,
+}
+
+fn default_token_url() -> String {
+    GOOGLE_TOKEN_URL.to_string()
+}
+
+fn default_revoke_url() -> String {
+    GOOGLE_REVOKE_URL.to_string()
+}
+
 /// FCM 앱 설정
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct FcmConfig {
+    /// 인증 방식
+    #[serde(default)]
+    pub auth_mode: AuthMode,
     /// Google OAuth 2.0 클라이언트 ID
     pub oauth_client_id: String,
-    /// OAuth 리다이렉트 URL
+    /// OAuth 리다이렉트 URL (콜백 서버의 호스트/포트/경로로 사용)
     pub oauth_redirect_url: String,
+    /// 리다이렉트 URL의 포트 대신 빈 포트를 자동 할당
+    #[serde(default)]
+    pub oauth_ephemeral_port: bool,
+    /// OAuth 클라이언트 시크릿 (직접 교환 시 사용)
+    #[serde(default)]
+    pub oauth_client_secret: String,
+    /// authorization code 교환 방식
+    #[serde(default)]
+    pub exchange_mode: ExchangeMode,
     /// 외부 토큰 교환 API URL
     pub exchange_code_url: String,
+    /// 외부 토큰 갱신 API URL (비어 있으면 relay 교환 토큰은 만료 후 다시 로그인)
+    #[serde(default)]
+    pub refresh_token_url: String,
+    /// 토큰 엔드포인트 URL (직접 교환 시 사용)
+    #[serde(default = "default_token_url")]
+    pub token_url: String,
+    /// 토큰 취소 엔드포인트 URL (로그아웃 시 사용)
+    #[serde(default = "default_revoke_url")]
+    pub revoke_url: String,
+    /// 서비스 계정 JSON 키 파일 경로
+    #[serde(default)]
+    pub service_account_key_path: String,
     /// Firebase 프로젝트 ID
     pub firebase_project_id: String,
+    /// 발송 재시도 정책
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// 허용할 Google Workspace 도메인 (id_token의 hd, 비어 있으면 검사 안 함)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+}
+
+/// FCM 발송 재시도 정책
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// 최대 시도 횟수 (첫 시도 포함, 1이면 재시도 안 함)
+    pub max_attempts: u32,
+    /// 첫 재시도 대기 시간 (ms)
+    pub initial_backoff_ms: u32,
+    /// 최대 대기 시간 (ms), Retry-After가 이보다 길면 재시도하지 않고 실패 상세에 남김
+    pub max_backoff_ms: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+        }
+    }
 }
 
 impl Default for FcmConfig {
     fn default() -> Self {
         Self {
+            auth_mode: AuthMode::OAuth,
             oauth_client_id:
                 "236719897156-fa7r2jko9e7es6t5nrreos1p66o2vpal.apps.googleusercontent.com"
                     .to_string(),
             oauth_redirect_url: "http://localhost:8080/callback".to_string(),
+            oauth_ephemeral_port: false,
+            oauth_client_secret: String::new(),
+            exchange_mode: ExchangeMode::Relay,
             exchange_code_url: "https://percent-config.111percent.net/ExchangeAuthorizationCode"
                 .to_string(),
+            refresh_token_url: String::new(),
+            token_url: default_token_url(),
+            revoke_url: default_revoke_url(),
+            service_account_key_path: String::new(),
             firebase_project_id: String::new(),
+            retry: RetryPolicy::default(),
+            allowed_domains: Vec::new(),
         }
     }
 }
@@ -32,8 +126,44 @@ impl Default for FcmConfig {
 impl FcmConfig {
     /// 설정이 유효한지 확인
     pub fn is_valid(&self) -> bool {
-        !self.oauth_client_id.is_empty()
-            && !self.exchange_code_url.is_empty()
-            && !self.firebase_project_id.is_empty()
+        match self.auth_mode {
+            AuthMode::OAuth => {
+                let exchange_configured = match self.exchange_mode {
+                    ExchangeMode::Relay => !self.exchange_code_url.is_empty(),
+                    ExchangeMode::Direct => !self.token_url.is_empty(),
+                };
+                !self.oauth_client_id.is_empty()
+                    && exchange_configured
+                    && !self.firebase_project_id.is_empty()
+            }
+            AuthMode::ServiceAccount => {
+                !self.service_account_key_path.is_empty() && !self.firebase_project_id.is_empty()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        let mut config = FcmConfig {
+            firebase_project_id: "dev-project".to_string(),
+            ..FcmConfig::default()
+        };
+        // 토큰 갱신 API URL은 로그인에 필요하지 않음
+        assert!(config.refresh_token_url.is_empty());
+        assert!(config.is_valid());
+
+        config.exchange_code_url = String::new();
+        assert!(!config.is_valid());
+
+        config.exchange_mode = ExchangeMode::Direct;
+        assert!(config.is_valid());
+
+        config.firebase_project_id = String::new();
+        assert!(!config.is_valid());
     }
 }
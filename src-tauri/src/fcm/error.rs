@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const FCM_ERROR_TYPE: &str = "type.googleapis.com/google.firebase.fcm.v1.FcmError";
+const BAD_REQUEST_TYPE: &str = "type.googleapis.com/google.rpc.BadRequest";
+
+/// FCM 에러 코드
+/// Reference: https://firebase.google.com/docs/reference/fcm/rest/v1/ErrorCode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FcmErrorCode {
+    /// 등록 해제되었거나 만료된 토큰
+    Unregistered,
+    /// 잘못된 요청 파라미터
+    InvalidArgument,
+    /// 토큰의 발신자 ID와 프로젝트가 일치하지 않음
+    SenderIdMismatch,
+    /// 발송 한도 초과
+    QuotaExceeded,
+    /// FCM 서버 일시적 사용 불가
+    Unavailable,
+    /// FCM 내부 오류
+    Internal,
+    /// APNs 인증서 또는 Web Push 인증 키 오류
+    ThirdPartyAuthError,
+    /// 액세스 토큰이 유효하지 않음
+    Unauthenticated,
+    /// 프로젝트 권한 없음
+    PermissionDenied,
+    /// 네트워크 오류 등으로 응답을 받지 못함
+    Network,
+    /// 알 수 없는 오류
+    Unknown,
+}
+
+impl FcmErrorCode {
+    /// FCM 문서상 재시도 가능한 에러인지 확인
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::QuotaExceeded | Self::Unavailable | Self::Internal
+        )
+    }
+
+    /// FcmError detail의 errorCode로부터 변환
+    fn from_fcm_error_code(code: &str) -> Option<Self> {
+        match code {
+            "UNREGISTERED" => Some(Self::Unregistered),
+            "INVALID_ARGUMENT" => Some(Self::InvalidArgument),
+            "SENDER_ID_MISMATCH" => Some(Self::SenderIdMismatch),
+            "QUOTA_EXCEEDED" => Some(Self::QuotaExceeded),
+            "UNAVAILABLE" => Some(Self::Unavailable),
+            "INTERNAL" => Some(Self::Internal),
+            "THIRD_PARTY_AUTH_ERROR" => Some(Self::ThirdPartyAuthError),
+            _ => None,
+        }
+    }
+
+    /// google.rpc 상태 문자열과 HTTP 상태 코드로부터 추정
+    fn from_status(status: &str, http_status: u16) -> Self {
+        match status {
+            "INVALID_ARGUMENT" => Self::InvalidArgument,
+            "NOT_FOUND" => Self::Unregistered,
+            "RESOURCE_EXHAUSTED" => Self::QuotaExceeded,
+            "UNAVAILABLE" => Self::Unavailable,
+            "INTERNAL" => Self::Internal,
+            "UNAUTHENTICATED" => Self::Unauthenticated,
+            "PERMISSION_DENIED" => Self::PermissionDenied,
+            _ => match http_status {
+                400 => Self::InvalidArgument,
+                401 => Self::Unauthenticated,
+                403 => Self::PermissionDenied,
+                404 => Self::Unregistered,
+                429 => Self::QuotaExceeded,
+                500 => Self::Internal,
+                503 => Self::Unavailable,
+                _ => Self::Unknown,
+            },
+        }
+    }
+}
+
+/// BadRequest 필드 위반 정보
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldViolation {
+    pub field: String,
+    pub description: String,
+}
+
+/// FCM 발송 에러
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FcmSendError {
+    pub code: FcmErrorCode,
+    /// HTTP 상태 코드 (응답을 받지 못한 경우 None)
+    #[serde(default)]
+    pub http_status: Option<u16>,
+    /// google.rpc 상태 (예: "NOT_FOUND")
+    #[serde(default)]
+    pub status: String,
+    pub message: String,
+    /// BadRequest 필드 위반 목록
+    #[serde(default)]
+    pub field_violations: Vec<FieldViolation>,
+    /// Retry-After 헤더 값 (초, HTTP-date는 남은 시간으로 변환)
+    #[serde(default)]
+    pub retry_after_secs: Option<u32>,
+}
+
+/// FCM API 에러 응답
+#[derive(Debug, Deserialize)]
+struct FcmApiError {
+    error: FcmApiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct FcmApiErrorDetail {
+    message: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    details: Vec<serde_json::Value>,
+}
+
+impl FcmSendError {
+    /// 응답을 받지 못한 요청 실패
+    pub fn network(message: String) -> Self {
+        Self {
+            code: FcmErrorCode::Network,
+            http_status: None,
+            status: String::new(),
+            message,
+            field_violations: Vec::new(),
+            retry_after_secs: None,
+        }
+    }
+
+    /// 응답 여부를 알 수 없는 실패 (발송 작업 중단 등)
+    pub fn unknown(message: String) -> Self {
+        Self {
+            code: FcmErrorCode::Unknown,
+            ..Self::network(message)
+        }
+    }
+
+    /// FCM 에러 응답 본문 파싱
+    pub fn from_response(http_status: u16, body: &str) -> Self {
+        let Ok(err) = serde_json::from_str::<FcmApiError>(body) else {
+            return Self {
+                code: FcmErrorCode::from_status("", http_status),
+                http_status: Some(http_status),
+                status: String::new(),
+                message: body.to_string(),
+                field_violations: Vec::new(),
+                retry_after_secs: None,
+            };
+        };
+
+        let mut fcm_error_code = None;
+        let mut field_violations = Vec::new();
+
+        for detail in &err.error.details {
+            match detail.get("@type").and_then(|t| t.as_str()) {
+                Some(FCM_ERROR_TYPE) => {
+                    fcm_error_code = detail
+                        .get("errorCode")
+                        .and_then(|c| c.as_str())
+                        .and_then(FcmErrorCode::from_fcm_error_code);
+                }
+                Some(BAD_REQUEST_TYPE) => {
+                    if let Some(violations) = detail.get("fieldViolations") {
+                        if let Ok(parsed) =
+                            serde_json::from_value::<Vec<FieldViolation>>(violations.clone())
+                        {
+                            field_violations.extend(parsed);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            code: fcm_error_code
+                .unwrap_or_else(|| FcmErrorCode::from_status(&err.error.status, http_status)),
+            http_status: Some(http_status),
+            status: err.error.status,
+            message: err.error.message,
+            field_violations,
+            retry_after_secs: None,
+        }
+    }
+}
+
+impl std::fmt::Display for FcmSendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.http_status {
+            Some(code) => write!(f, "발송 실패 ({}): {}", code, self.message)?,
+            None => write!(f, "{}", self.message)?,
+        }
+        for violation in &self.field_violations {
+            write!(f, "\n- {}: {}", violation.field, violation.description)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FcmSendError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fcm_error_detail() {
+        let body = r#"{
+            "error": {
+                "code": 404,
+                "message": "Requested entity was not found.",
+                "status": "NOT_FOUND",
+                "details": [{
+                    "@type": "type.googleapis.com/google.firebase.fcm.v1.FcmError",
+                    "errorCode": "UNREGISTERED"
+                }]
+            }
+        }"#;
+        let err = FcmSendError::from_response(404, body);
+        assert_eq!(err.code, FcmErrorCode::Unregistered);
+        assert_eq!(err.status, "NOT_FOUND");
+        assert_eq!(err.http_status, Some(404));
+    }
+
+    #[test]
+    fn test_bad_request_field_violations() {
+        let body = r#"{
+            "error": {
+                "code": 400,
+                "message": "Invalid value at 'message.android.priority'",
+                "status": "INVALID_ARGUMENT",
+                "details": [{
+                    "@type": "type.googleapis.com/google.rpc.BadRequest",
+                    "fieldViolations": [{
+                        "field": "message.android.priority",
+                        "description": "Invalid value"
+                    }]
+                }]
+            }
+        }"#;
+        let err = FcmSendError::from_response(400, body);
+        assert_eq!(err.code, FcmErrorCode::InvalidArgument);
+        assert_eq!(err.field_violations.len(), 1);
+        assert_eq!(err.field_violations[0].field, "message.android.priority");
+    }
+
+    #[test]
+    fn test_status_fallback() {
+        let body = r#"{"error": {"code": 403, "message": "denied", "status": "PERMISSION_DENIED", "details": [{"@type": "type.googleapis.com/google.firebase.fcm.v1.FcmError", "errorCode": "SENDER_ID_MISMATCH"}]}}"#;
+        assert_eq!(
+            FcmSendError::from_response(403, body).code,
+            FcmErrorCode::SenderIdMismatch
+        );
+        assert_eq!(
+            FcmSendError::from_response(503, "Service Unavailable").code,
+            FcmErrorCode::Unavailable
+        );
+    }
+}
@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Google OAuth 2.0 토큰 엔드포인트
+pub const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Google OAuth 2.0 토큰 취소 엔드포인트
+pub const GOOGLE_REVOKE_URL: &str = "https://oauth2.googleapis.com/revoke";
+
 /// 외부 API로 토큰 교환 요청
 #[derive(Debug, Serialize)]
 pub struct ExchangeCodeRequest {
@@ -9,6 +15,12 @@ pub struct ExchangeCodeRequest {
     pub code_verifier: String,
 }
 
+/// 외부 API로 토큰 갱신 요청
+#[derive(Debug, Serialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
 /// 외부 API 토큰 교환 응답
 #[derive(Debug, Deserialize)]
 pub struct ExchangeCodeResponse {
@@ -34,18 +46,131 @@ pub async fn exchange_code_via_external_api(
         code_verifier: code_verifier.to_string(),
     };
 
-    let client = reqwest::Client::builder()
+    post_token_request(api_url, &payload, "토큰 교환").await
+}
+
+/// 외부 API를 통해 refresh_token으로 access_token 갱신
+pub async fn refresh_token_via_external_api(
+    api_url: &str,
+    refresh_token: &str,
+) -> Result<ExchangeCodeResponse, String> {
+    let payload = RefreshTokenRequest {
+        refresh_token: refresh_token.to_string(),
+    };
+
+    post_token_request(api_url, &payload, "토큰 갱신").await
+}
+
+/// Google 토큰 엔드포인트로 authorization code를 직접 교환
+pub async fn exchange_code_direct(
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+    redirect_uri: &str,
+    code_verifier: &str,
+) -> Result<ExchangeCodeResponse, String> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("client_id", client_id),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        ("code_verifier", code_verifier),
+    ];
+    if !client_secret.is_empty() {
+        form.push(("client_secret", client_secret));
+    }
+
+    let resp = http_client()?
+        .post(token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("토큰 교환 요청 실패: {}", e))?;
+
+    read_token_response(resp, "토큰 교환").await
+}
+
+/// Google 토큰 엔드포인트에서 refresh_token으로 access_token 직접 갱신
+pub async fn refresh_token_direct(
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    refresh_token: &str,
+) -> Result<ExchangeCodeResponse, String> {
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("client_id", client_id),
+        ("refresh_token", refresh_token),
+    ];
+    if !client_secret.is_empty() {
+        form.push(("client_secret", client_secret));
+    }
+
+    let resp = http_client()?
+        .post(token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("토큰 갱신 요청 실패: {}", e))?;
+
+    read_token_response(resp, "토큰 갱신").await
+}
+
+/// 토큰 취소 (이미 무효화된 토큰은 취소된 것으로 간주)
+pub async fn revoke_token(revoke_url: &str, token: &str) -> Result<(), String> {
+    let resp = http_client()?
+        .post(revoke_url)
+        .form(&[("token", token)])
+        .send()
+        .await
+        .map_err(|e| format!("토큰 취소 요청 실패: {}", e))?;
+
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let body = resp.text().await.unwrap_or_default();
+    if status.as_u16() == 400 && body.contains("invalid_token") {
+        return Ok(());
+    }
+
+    Err(format!(
+        "토큰 취소 실패: HTTP {} | {}",
+        status.as_u16(),
+        body
+    ))
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
         .build()
-        .map_err(|e| format!("HTTP 클라이언트 생성 실패: {}", e))?;
+        .map_err(|e| format!("HTTP 클라이언트 생성 실패: {}", e))
+}
 
-    let resp = client
+/// 외부 API에 JSON 요청을 보내고 토큰 응답 파싱
+async fn post_token_request<T: Serialize>(
+    api_url: &str,
+    payload: &T,
+    action: &str,
+) -> Result<ExchangeCodeResponse, String> {
+    let resp = http_client()?
         .post(api_url)
-        .json(&payload)
+        .json(payload)
         .send()
         .await
-        .map_err(|e| format!("토큰 교환 요청 실패: {}", e))?;
+        .map_err(|e| format!("{} 요청 실패: {}", action, e))?;
+
+    read_token_response(resp, action).await
+}
 
+/// 토큰 응답 파싱
+async fn read_token_response(
+    resp: reqwest::Response,
+    action: &str,
+) -> Result<ExchangeCodeResponse, String> {
     let status = resp.status();
     let body = resp
         .text()
@@ -54,7 +179,8 @@ pub async fn exchange_code_via_external_api(
 
     if !status.is_success() {
         return Err(format!(
-            "토큰 교환 실패: HTTP {} | {}",
+            "{} 실패: HTTP {} | {}",
+            action,
             status.as_u16(),
             body
         ));
@@ -69,3 +195,140 @@ pub async fn exchange_code_via_external_api(
 
     Ok(token_response)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcm::mock_server::MockServer;
+
+    fn token_body() -> String {
+        serde_json::json!({
+            "access_token": "ya29.access",
+            "refresh_token": "1//refresh",
+            "id_token": "header.payload.signature",
+            "expires_in": 3599,
+            "token_type": "Bearer",
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_direct() {
+        let server = MockServer::start(|_| async { (200, token_body()) }).await;
+
+        let response = exchange_code_direct(
+            &format!("{}/token", server.url),
+            "client-id",
+            "secret",
+            "4/0Ab+c",
+            "http://127.0.0.1:8080/callback",
+            "verifier",
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.access_token, "ya29.access");
+        assert_eq!(response.refresh_token, "1//refresh");
+        assert_eq!(response.expires_in, 3599);
+
+        let requests = server.requests();
+        let request = &requests[0];
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/token");
+        assert_eq!(
+            request.header("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(
+            request.form("grant_type").as_deref(),
+            Some("authorization_code")
+        );
+        assert_eq!(request.form("client_id").as_deref(), Some("client-id"));
+        assert_eq!(request.form("client_secret").as_deref(), Some("secret"));
+        assert_eq!(request.form("code").as_deref(), Some("4/0Ab+c"));
+        assert_eq!(
+            request.form("redirect_uri").as_deref(),
+            Some("http://127.0.0.1:8080/callback")
+        );
+        assert_eq!(request.form("code_verifier").as_deref(), Some("verifier"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_direct_without_secret() {
+        let server = MockServer::start(|_| async {
+            let body = serde_json::json!({
+                "access_token": "ya29.refreshed",
+                "expires_in": 3599,
+                "token_type": "Bearer",
+            });
+            (200, body.to_string())
+        })
+        .await;
+
+        let response = refresh_token_direct(&server.url, "client-id", "", "1//refresh")
+            .await
+            .unwrap();
+        assert_eq!(response.access_token, "ya29.refreshed");
+        assert!(response.refresh_token.is_empty());
+
+        let request = &server.requests()[0];
+        assert_eq!(request.form("grant_type").as_deref(), Some("refresh_token"));
+        assert_eq!(request.form("refresh_token").as_deref(), Some("1//refresh"));
+        assert_eq!(request.form("client_id").as_deref(), Some("client-id"));
+        // 시크릿이 비어 있으면 보내지 않음
+        assert!(request.form("client_secret").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_direct_error_response() {
+        let server = MockServer::start(|_| async {
+            let body = serde_json::json!({
+                "error": "invalid_grant",
+                "error_description": "Bad Request",
+            });
+            (400, body.to_string())
+        })
+        .await;
+
+        let err = refresh_token_direct(&server.url, "client-id", "secret", "1//expired")
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("토큰 갱신 실패: HTTP 400"));
+        assert!(err.contains("invalid_grant"));
+
+        let err = exchange_code_direct(&server.url, "client-id", "", "code", "uri", "verifier")
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("토큰 교환 실패: HTTP 400"));
+    }
+
+    #[tokio::test]
+    async fn test_direct_invalid_success_body() {
+        // 성공 응답이지만 토큰 응답 형식이 아님
+        let server = MockServer::start(|_| async {
+            (
+                200,
+                serde_json::json!({ "error": "invalid_grant" }).to_string(),
+            )
+        })
+        .await;
+        let err = refresh_token_direct(&server.url, "client-id", "", "1//refresh")
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("응답 파싱 실패"));
+
+        // access_token이 빈 값
+        let server = MockServer::start(|_| async {
+            let body = serde_json::json!({
+                "access_token": "",
+                "expires_in": 3599,
+                "token_type": "Bearer",
+            });
+            (200, body.to_string())
+        })
+        .await;
+        let err = refresh_token_direct(&server.url, "client-id", "", "1//refresh")
+            .await
+            .unwrap_err();
+        assert_eq!(err, "응답에 access_token이 없습니다");
+    }
+}
@@ -1,6 +1,13 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use super::client::multicast_tokens;
+use super::error::FcmErrorCode;
+use super::message::{MessageType, SendMode, SendRequest, SendResult, SendStatus, TokenResult};
+use super::raw::from_raw;
+use super::webpush::WebpushSettings;
 
 /// 발송 히스토리 항목
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -8,27 +15,145 @@ use specta::Type;
 pub struct HistoryEntry {
     pub id: String,
     pub timestamp: DateTime<Utc>,
-    /// "single" or "topic"
+    /// 발송한 프로필 ID
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    /// "single", "topic", "condition", "multicast" or "raw"
     pub message_type: String,
+    /// 발송 대상 (토큰, 토픽명, 조건식 또는 토큰 수 요약)
+    #[serde(default)]
+    pub target: String,
+    /// 발송 모드
+    #[serde(default)]
+    pub send_mode: SendMode,
     pub title: String,
     pub body: String,
-    pub success: bool,
+    /// 커스텀 데이터 페이로드
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+    /// 알림 이미지 URL
+    #[serde(default)]
+    pub image: Option<String>,
+    /// FCM 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+    /// Web Push 설정
+    #[serde(default)]
+    pub webpush: Option<WebpushSettings>,
+    /// 발송 결과 상태
+    // 이전 버전 히스토리는 `success: bool`로 저장되어 있음
+    #[serde(alias = "success", deserialize_with = "deserialize_status")]
+    pub status: SendStatus,
     pub details: String,
+    /// 실패 시 FCM 에러 코드
+    #[serde(default)]
+    pub error_code: Option<FcmErrorCode>,
+    /// FCM API 호출 시도 횟수
+    #[serde(default)]
+    pub attempts: u32,
+    /// 멀티캐스트 토큰별 결과
+    #[serde(default)]
+    pub token_results: Vec<TokenResult>,
+    /// raw 모드로 발송한 요청 본문 (JSON)
+    #[serde(default)]
+    pub raw: Option<String>,
 }
 
 impl HistoryEntry {
-    /// 새 히스토리 항목 생성
-    pub fn new(message_type: &str, title: &str, body: &str, success: bool, details: &str) -> Self {
+    /// 발송 요청과 결과로부터 히스토리 항목 생성
+    pub fn new(request: &SendRequest, result: &SendResult) -> Self {
+        let (message_type, target) = match request.message_type {
+            MessageType::Single => ("single", request.token.clone().unwrap_or_default()),
+            MessageType::Topic => ("topic", request.topic.clone().unwrap_or_default()),
+            MessageType::Condition => ("condition", request.condition.clone().unwrap_or_default()),
+            MessageType::Multicast => (
+                "multicast",
+                format!("{}개 토큰", multicast_tokens(&request.tokens).len()),
+            ),
+        };
+
+        let (title, body, image) = match (request.send_mode, &request.message) {
+            (SendMode::Notification, Some(m)) => (m.title.clone(), m.body.clone(), m.image.clone()),
+            _ => (String::new(), String::new(), None),
+        };
+
+        Self {
+            message_type: message_type.to_string(),
+            target,
+            send_mode: request.send_mode,
+            title,
+            body,
+            data: request.data.clone(),
+            image,
+            analytics_label: request.analytics_label.clone(),
+            webpush: request.webpush.clone(),
+            ..Self::from_result(result)
+        }
+    }
+
+    /// raw 요청 본문과 결과로부터 히스토리 항목 생성
+    ///
+    /// 요약 필드는 구조화된 요청으로 변환할 수 있는 범위에서 채운다.
+    pub fn from_raw(document: &serde_json::Value, result: &SendResult) -> Self {
+        let raw = serde_json::to_string_pretty(document).ok();
+        match from_raw(document) {
+            Ok(conversion) => Self {
+                raw,
+                ..Self::new(&conversion.request, result)
+            },
+            Err(_) => Self {
+                message_type: "raw".to_string(),
+                raw,
+                ..Self::from_result(result)
+            },
+        }
+    }
+
+    /// 발송 결과만 채운 항목
+    fn from_result(result: &SendResult) -> Self {
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
-            message_type: message_type.to_string(),
-            title: title.to_string(),
-            body: body.to_string(),
-            success,
-            details: details.to_string(),
+            profile_id: None,
+            message_type: String::new(),
+            target: String::new(),
+            send_mode: SendMode::default(),
+            title: String::new(),
+            body: String::new(),
+            data: BTreeMap::new(),
+            image: None,
+            analytics_label: None,
+            webpush: None,
+            status: result.status,
+            details: result.details.clone(),
+            error_code: result.error.as_ref().map(|e| e.code),
+            attempts: result.attempts,
+            token_results: result.token_results.clone(),
+            raw: None,
         }
     }
+
+    /// 발송한 프로필로 태그
+    pub fn with_profile(mut self, profile_id: &str) -> Self {
+        self.profile_id = Some(profile_id.to_string());
+        self
+    }
+}
+
+/// 발송 결과 상태 역직렬화 (이전 버전 히스토리의 `success: bool`은 발송/실패로 변환)
+fn deserialize_status<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SendStatus, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Status {
+        Status(SendStatus),
+        Legacy(bool),
+    }
+
+    Ok(match Status::deserialize(deserializer)? {
+        Status::Status(status) => status,
+        Status::Legacy(true) => SendStatus::Sent,
+        Status::Legacy(false) => SendStatus::Failed,
+    })
 }
 
 /// 히스토리 목록 (최대 100개)
@@ -69,3 +194,51 @@ impl HistoryList {
         self.entries.iter().find(|e| e.id == id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_roundtrip() {
+        let entry = HistoryEntry::from_result(&SendResult::validated("ok".to_string()));
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["status"], "validated");
+        assert!(json.get("success").is_none());
+
+        let entry: HistoryEntry = serde_json::from_value(json).unwrap();
+        assert_eq!(entry.status, SendStatus::Validated);
+    }
+
+    #[test]
+    fn test_legacy_success_field() {
+        let legacy = |success: bool| {
+            serde_json::json!({
+                "id": "1",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "messageType": "single",
+                "title": "t",
+                "body": "b",
+                "success": success,
+                "details": "",
+            })
+        };
+
+        let entry: HistoryEntry = serde_json::from_value(legacy(true)).unwrap();
+        assert_eq!(entry.status, SendStatus::Sent);
+        let entry: HistoryEntry = serde_json::from_value(legacy(false)).unwrap();
+        assert_eq!(entry.status, SendStatus::Failed);
+    }
+
+    #[test]
+    fn test_multicast_target_count() {
+        let request: SendRequest = serde_json::from_value(serde_json::json!({
+            "messageType": "multicast",
+            "tokens": ["a", " a ", "", "b", "  "],
+        }))
+        .unwrap();
+        let entry = HistoryEntry::new(&request, &SendResult::success("ok".to_string()));
+        assert_eq!(entry.message_type, "multicast");
+        assert_eq!(entry.target, "2개 토큰");
+    }
+}
@@ -0,0 +1,132 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// id_token(JWT)의 클레임
+///
+/// 토큰은 Google 토큰 엔드포인트에서 TLS로 직접 받은 것이므로 서명은 검증하지 않는다.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdTokenClaims {
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub picture: Option<String>,
+    /// Google Workspace 호스팅 도메인 (개인 계정은 없음)
+    #[serde(default)]
+    pub hd: Option<String>,
+    #[serde(default)]
+    pub exp: Option<i64>,
+}
+
+/// id_token의 payload 디코딩
+pub fn decode_claims(id_token: &str) -> Result<IdTokenClaims, String> {
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| "id_token 형식이 올바르지 않습니다".to_string())?;
+
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| format!("id_token 디코딩 실패: {}", e))?;
+
+    serde_json::from_slice(&bytes).map_err(|e| format!("id_token 파싱 실패: {}", e))
+}
+
+/// 로그인한 계정 정보 (id_token 클레임)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub picture: Option<String>,
+    pub hd: Option<String>,
+    /// id_token 만료 시각
+    pub exp: Option<DateTime<Utc>>,
+}
+
+impl AccountInfo {
+    /// id_token에서 계정 정보 추출
+    pub fn from_id_token(id_token: &str) -> Result<Self, String> {
+        let claims = decode_claims(id_token)?;
+        Ok(Self {
+            email: claims.email,
+            name: claims.name,
+            picture: claims.picture,
+            hd: claims.hd,
+            exp: claims.exp.and_then(|exp| DateTime::from_timestamp(exp, 0)),
+        })
+    }
+
+    /// 허용 도메인 목록과 호스팅 도메인이 다르면 경고 메시지 반환 (목록이 비어 있으면 검사 안 함)
+    pub fn domain_warning(&self, allowed_domains: &[String]) -> Option<String> {
+        if allowed_domains.is_empty() {
+            return None;
+        }
+
+        match self.hd.as_deref() {
+            Some(hd) if allowed_domains.iter().any(|d| d.eq_ignore_ascii_case(hd)) => None,
+            Some(hd) => Some(format!(
+                "허용되지 않은 도메인의 계정입니다: {} (허용: {})",
+                hd,
+                allowed_domains.join(", ")
+            )),
+            None => Some(format!(
+                "조직 도메인이 없는 개인 계정입니다 (허용: {})",
+                allowed_domains.join(", ")
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_token(claims: &str) -> String {
+        format!(
+            "{}.{}.signature",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256"}"#),
+            URL_SAFE_NO_PAD.encode(claims)
+        )
+    }
+
+    #[test]
+    fn test_decode_email() {
+        let token = make_token(r#"{"email":"dev@example.com","sub":"123"}"#);
+        let claims = decode_claims(&token).unwrap();
+        assert_eq!(claims.email.as_deref(), Some("dev@example.com"));
+    }
+
+    #[test]
+    fn test_invalid_token() {
+        assert!(decode_claims("not-a-jwt").is_err());
+        assert!(decode_claims("a.!!!.c").is_err());
+    }
+
+    #[test]
+    fn test_account_info() {
+        let token = make_token(
+            r#"{"email":"dev@corp.com","name":"Dev","picture":"https://example.com/a.png","hd":"corp.com","exp":1700000000}"#,
+        );
+        let info = AccountInfo::from_id_token(&token).unwrap();
+        assert_eq!(info.name.as_deref(), Some("Dev"));
+        assert_eq!(info.hd.as_deref(), Some("corp.com"));
+        assert_eq!(info.exp.unwrap().timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_domain_warning() {
+        let token = make_token(r#"{"email":"dev@corp.com","hd":"corp.com"}"#);
+        let info = AccountInfo::from_id_token(&token).unwrap();
+        assert!(info.domain_warning(&[]).is_none());
+        assert!(info.domain_warning(&["CORP.com".to_string()]).is_none());
+        assert!(info.domain_warning(&["other.com".to_string()]).is_some());
+
+        let personal =
+            AccountInfo::from_id_token(&make_token(r#"{"email":"a@gmail.com"}"#)).unwrap();
+        assert!(personal.domain_warning(&["corp.com".to_string()]).is_some());
+    }
+}
@@ -0,0 +1,461 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::apns::{ApnsPushType, MAX_APNS_PAYLOAD_BYTES};
+use super::client::{multicast_tokens, payload_sizes, MAX_MULTICAST_TOKENS};
+use super::condition::parse_condition;
+use super::message::{MessageType, SendMode, SendRequest};
+use super::raw::MAX_MESSAGE_BYTES;
+use super::template::TemplateRenderError;
+use super::validate::{validate_data, validate_topic_name};
+
+/// Web Push 페이로드 최대 크기 (바이트)
+const MAX_WEBPUSH_PAYLOAD_BYTES: usize = 4096;
+
+/// 린트 항목 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    /// 발송 대상 누락 또는 형식 오류
+    InvalidTarget,
+    /// 예약된 데이터 키 사용
+    ReservedDataKey,
+    /// 알림 모드인데 제목/내용이 비어 있음
+    EmptyNotification,
+    /// 플랫폼 설정 형식 오류
+    InvalidSettings,
+    /// 페이로드 크기 제한 초과
+    PayloadTooLarge,
+    /// 데이터 전용 메시지의 Android high 우선순위 (FCM이 우선순위를 낮출 수 있음)
+    DataOnlyHighPriority,
+    /// APNs 우선순위/푸시 타입 불일치
+    ApnsPriorityMismatch,
+    /// 템플릿 렌더링 실패
+    TemplateRender,
+}
+
+/// 린트 결과 항목
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub code: LintCode,
+    /// 문제가 있는 필드 (예: "topic", "android.priority")
+    pub field: String,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(code: LintCode, field: &str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// "field: message" 형식의 설정 검증 에러를 필드와 메시지로 분리
+    fn from_settings_error(error: String) -> Self {
+        match error.split_once(": ") {
+            Some((field, message)) if !field.contains(' ') => {
+                Self::new(LintCode::InvalidSettings, field, message)
+            }
+            _ => Self::new(LintCode::InvalidSettings, "", error),
+        }
+    }
+}
+
+/// 직렬화된 페이로드 크기 (바이트, 대상 필드 제외)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadSizes {
+    /// FCM 메시지 전체
+    pub message: u32,
+    /// Android 기기에 전달되는 부분
+    pub android: u32,
+    /// APNs 페이로드 (notification/data 병합 후)
+    pub apns: u32,
+    /// Web Push 페이로드
+    pub webpush: u32,
+}
+
+/// 발송 전 린트 결과
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    /// 발송이 실패하는 문제
+    pub errors: Vec<LintIssue>,
+    /// 발송은 되지만 의도와 다를 수 있는 사항
+    pub warnings: Vec<LintIssue>,
+    /// 메시지를 만들 수 없으면 None
+    pub sizes: Option<PayloadSizes>,
+    /// 템플릿 렌더링 에러 (있으면 요청 자체는 검사하지 않음)
+    #[serde(default)]
+    pub template_errors: Vec<TemplateRenderError>,
+}
+
+impl LintReport {
+    /// 템플릿 렌더링 실패 (에러마다 렌더링 에러 항목을 함께 채움)
+    pub fn template_failure(errors: Vec<TemplateRenderError>) -> Self {
+        Self {
+            errors: errors
+                .iter()
+                .map(|e| {
+                    LintIssue::new(
+                        LintCode::TemplateRender,
+                        e.field.as_deref().unwrap_or_default(),
+                        e.message.clone(),
+                    )
+                })
+                .collect(),
+            warnings: Vec::new(),
+            sizes: None,
+            template_errors: errors,
+        }
+    }
+}
+
+/// 발송 요청 린트 (FCM 호출 없이 로컬에서 검사)
+pub fn lint_request(request: &SendRequest) -> LintReport {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    check_target(request, &mut errors);
+
+    // 키마다 따로 검사하여 예약된 키를 모두 보고
+    for (key, value) in &request.data {
+        if let Err(e) = validate_data(&BTreeMap::from([(key.clone(), value.clone())])) {
+            errors.push(LintIssue::new(
+                LintCode::ReservedDataKey,
+                &format!("data.{}", key),
+                e,
+            ));
+        }
+    }
+
+    if request.send_mode == SendMode::Notification {
+        match &request.message {
+            Some(message) => {
+                let empty: Vec<&str> = [("title", &message.title), ("body", &message.body)]
+                    .into_iter()
+                    .filter(|(_, value)| value.trim().is_empty())
+                    .map(|(name, _)| name)
+                    .collect();
+                if !empty.is_empty() {
+                    warnings.push(LintIssue::new(
+                        LintCode::EmptyNotification,
+                        &format!("message.{}", empty[0]),
+                        format!(
+                            "알림의 {}이(가) 비어 있어 기기에 빈 알림이 표시될 수 있습니다",
+                            empty.join("/")
+                        ),
+                    ));
+                }
+            }
+            None => errors.push(LintIssue::new(
+                LintCode::EmptyNotification,
+                "message",
+                "알림 모드에는 제목/내용이 필요합니다",
+            )),
+        }
+    }
+
+    check_platforms(request, &mut errors, &mut warnings);
+
+    let sizes = if errors.is_empty() {
+        match payload_sizes(request) {
+            Ok(sizes) => Some(sizes),
+            Err(e) => {
+                errors.push(LintIssue::from_settings_error(e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(sizes) = &sizes {
+        check_sizes(sizes, &mut errors, &mut warnings);
+    }
+
+    LintReport {
+        errors,
+        warnings,
+        sizes,
+        template_errors: Vec::new(),
+    }
+}
+
+/// 메시지 타입별 발송 대상 검사
+fn check_target(request: &SendRequest, errors: &mut Vec<LintIssue>) {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    };
+
+    let result = match request.message_type {
+        MessageType::Single => non_empty(&request.token)
+            .map(|_| ())
+            .ok_or_else(|| ("token", "디바이스 토큰이 없습니다".to_string())),
+        MessageType::Topic => match non_empty(&request.topic) {
+            Some(topic) => validate_topic_name(&topic).map_err(|e| ("topic", e)),
+            None => Err(("topic", "토픽 이름이 없습니다".to_string())),
+        },
+        MessageType::Condition => match non_empty(&request.condition) {
+            Some(condition) => parse_condition(&condition)
+                .map(|_| ())
+                .map_err(|e| ("condition", format!("조건식 오류: {}", e))),
+            None => Err(("condition", "토픽 조건식이 없습니다".to_string())),
+        },
+        MessageType::Multicast => {
+            let count = multicast_tokens(&request.tokens).len();
+            if count == 0 {
+                Err(("tokens", "디바이스 토큰이 없습니다".to_string()))
+            } else if count > MAX_MULTICAST_TOKENS {
+                Err((
+                    "tokens",
+                    format!(
+                        "멀티캐스트는 최대 {}개의 토큰까지 가능합니다 (현재 {}개)",
+                        MAX_MULTICAST_TOKENS, count
+                    ),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    };
+
+    if let Err((field, message)) = result {
+        errors.push(LintIssue::new(LintCode::InvalidTarget, field, message));
+    }
+}
+
+/// 플랫폼 설정 검증과 발송 모드에 따른 우선순위 검사
+fn check_platforms(
+    request: &SendRequest,
+    errors: &mut Vec<LintIssue>,
+    warnings: &mut Vec<LintIssue>,
+) {
+    let data_only = request.send_mode == SendMode::DataOnly;
+
+    if let Some(android) = &request.android {
+        if let Err(e) = android.validate() {
+            errors.push(LintIssue::from_settings_error(e));
+        }
+        if data_only && android.priority == "high" {
+            warnings.push(LintIssue::new(
+                LintCode::DataOnlyHighPriority,
+                "android.priority",
+                "데이터 전용 메시지를 high 우선순위로 보냅니다. 기기에 알림을 표시하지 않으면 FCM이 이후 메시지의 우선순위를 낮출 수 있습니다",
+            ));
+        }
+    }
+
+    if let Some(apns) = &request.apns {
+        match apns.validate() {
+            // background 푸시의 우선순위 오류는 불일치 항목으로 분류
+            Err(e)
+                if apns.push_type == Some(ApnsPushType::Background)
+                    && !apns.priority.is_empty()
+                    && apns.priority != "5" =>
+            {
+                errors.push(LintIssue::new(
+                    LintCode::ApnsPriorityMismatch,
+                    "apns.priority",
+                    e.trim_start_matches("apns.priority: ").to_string(),
+                ))
+            }
+            Err(e) => errors.push(LintIssue::from_settings_error(e)),
+            Ok(()) => {}
+        }
+
+        if data_only && apns.priority == "10" {
+            warnings.push(LintIssue::new(
+                LintCode::ApnsPriorityMismatch,
+                "apns.priority",
+                "데이터 전용 메시지는 background 푸시로 전송되므로 우선순위 10 대신 5로 전송됩니다",
+            ));
+        }
+        match apns.push_type {
+            Some(push_type) if data_only && push_type != ApnsPushType::Background => {
+                warnings.push(LintIssue::new(
+                    LintCode::ApnsPriorityMismatch,
+                    "apns.pushType",
+                    "데이터 전용 메시지는 background 푸시 타입으로 전송됩니다",
+                ))
+            }
+            Some(ApnsPushType::Background) if !data_only => warnings.push(LintIssue::new(
+                LintCode::ApnsPriorityMismatch,
+                "apns.pushType",
+                "background 푸시 타입에서는 알림이 표시되지 않을 수 있습니다",
+            )),
+            _ => {}
+        }
+    }
+
+    if let Some(webpush) = &request.webpush {
+        if let Err(e) = webpush.validate() {
+            errors.push(LintIssue::from_settings_error(e));
+        }
+    }
+}
+
+/// 크기 제한 검사 (FCM 메시지 한도는 에러, 플랫폼 한도는 경고)
+fn check_sizes(sizes: &PayloadSizes, errors: &mut Vec<LintIssue>, warnings: &mut Vec<LintIssue>) {
+    let too_large = |field: &str, name: &str, size: u32, limit: usize| {
+        LintIssue::new(
+            LintCode::PayloadTooLarge,
+            field,
+            format!(
+                "{}가 {}바이트로 제한({}바이트)을 넘습니다",
+                name, size, limit
+            ),
+        )
+    };
+
+    if sizes.message as usize > MAX_MESSAGE_BYTES {
+        errors.push(too_large("", "메시지", sizes.message, MAX_MESSAGE_BYTES));
+    }
+    if sizes.apns as usize > MAX_APNS_PAYLOAD_BYTES {
+        warnings.push(too_large(
+            "apns",
+            "APNs 페이로드",
+            sizes.apns,
+            MAX_APNS_PAYLOAD_BYTES,
+        ));
+    }
+    if sizes.webpush as usize > MAX_WEBPUSH_PAYLOAD_BYTES {
+        warnings.push(too_large(
+            "webpush",
+            "Web Push 페이로드",
+            sizes.webpush,
+            MAX_WEBPUSH_PAYLOAD_BYTES,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcm::message::FcmMessage;
+
+    fn request(send_mode: SendMode) -> SendRequest {
+        serde_json::from_value(serde_json::json!({
+            "messageType": "topic",
+            "sendMode": send_mode,
+            "message": { "title": "title", "body": "body" },
+            "topic": "news",
+        }))
+        .unwrap()
+    }
+
+    fn codes(issues: &[LintIssue]) -> Vec<LintCode> {
+        issues.iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn test_clean_request() {
+        let report = lint_request(&request(SendMode::Notification));
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+        let sizes = report.sizes.unwrap();
+        assert!(sizes.message > 0 && sizes.apns > 0);
+    }
+
+    #[test]
+    fn test_target_and_data_errors() {
+        let mut req = request(SendMode::Notification);
+        req.topic = Some("bad topic".to_string());
+        req.data = BTreeMap::from([
+            ("from".to_string(), "x".to_string()),
+            ("gcm.key".to_string(), "x".to_string()),
+        ]);
+        let report = lint_request(&req);
+        assert_eq!(
+            codes(&report.errors),
+            vec![
+                LintCode::InvalidTarget,
+                LintCode::ReservedDataKey,
+                LintCode::ReservedDataKey
+            ]
+        );
+        assert!(report.sizes.is_none());
+    }
+
+    #[test]
+    fn test_notification_warnings() {
+        let mut req = request(SendMode::Notification);
+        req.message = Some(FcmMessage {
+            title: " ".to_string(),
+            body: String::new(),
+            image: None,
+        });
+        let report = lint_request(&req);
+        assert_eq!(codes(&report.warnings), vec![LintCode::EmptyNotification]);
+        assert_eq!(report.warnings[0].field, "message.title");
+    }
+
+    #[test]
+    fn test_priority_checks() {
+        let mut req = request(SendMode::DataOnly);
+        req.android = serde_json::from_value(serde_json::json!({
+            "priority": "high",
+            "channelId": "",
+        }))
+        .unwrap();
+        req.apns = serde_json::from_value(serde_json::json!({ "priority": "10" })).unwrap();
+        let report = lint_request(&req);
+        assert_eq!(
+            codes(&report.warnings),
+            vec![
+                LintCode::DataOnlyHighPriority,
+                LintCode::ApnsPriorityMismatch
+            ]
+        );
+
+        let mut req = request(SendMode::Notification);
+        req.apns = serde_json::from_value(serde_json::json!({
+            "priority": "10",
+            "pushType": "background",
+        }))
+        .unwrap();
+        let report = lint_request(&req);
+        assert_eq!(codes(&report.errors), vec![LintCode::ApnsPriorityMismatch]);
+        assert_eq!(report.errors[0].field, "apns.priority");
+
+        // 우선순위를 지정하지 않으면 5로 전송
+        req.apns.as_mut().unwrap().priority = String::new();
+        assert!(lint_request(&req).errors.is_empty());
+    }
+
+    #[test]
+    fn test_template_failure() {
+        let errors: Vec<TemplateRenderError> = serde_json::from_value(serde_json::json!([
+            { "kind": "missing_variable", "variable": "name", "field": "title", "message": "필수 변수 값이 없습니다: name" },
+            { "kind": "syntax", "message": "닫히지 않은 {{" },
+        ]))
+        .unwrap();
+
+        let report = LintReport::template_failure(errors);
+        assert_eq!(codes(&report.errors), vec![LintCode::TemplateRender; 2]);
+        assert_eq!(report.errors[0].field, "title");
+        assert_eq!(report.errors[1].field, "");
+        assert_eq!(report.template_errors.len(), 2);
+        assert_eq!(report.template_errors[0].variable.as_deref(), Some("name"));
+        assert!(report.sizes.is_none());
+    }
+
+    #[test]
+    fn test_oversized_message() {
+        let mut req = request(SendMode::Notification);
+        req.data = BTreeMap::from([("blob".to_string(), "x".repeat(MAX_MESSAGE_BYTES))]);
+        let report = lint_request(&req);
+        assert_eq!(codes(&report.errors), vec![LintCode::PayloadTooLarge]);
+        assert_eq!(
+            codes(&report.warnings),
+            vec![LintCode::PayloadTooLarge, LintCode::PayloadTooLarge]
+        );
+    }
+}
@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use super::android::AndroidSettings;
+use super::apns::ApnsSettings;
+use super::error::{FcmErrorCode, FcmSendError};
+use super::template::{TemplateInput, TemplateRenderError};
+use super::webpush::WebpushSettings;
 
 /// FCM 메시지 발송 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
@@ -9,6 +16,21 @@ pub enum MessageType {
     Single,
     /// 토픽
     Topic,
+    /// 토픽 조건식
+    Condition,
+    /// 여러 디바이스 (멀티캐스트)
+    Multicast,
+}
+
+/// FCM 메시지 발송 모드
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SendMode {
+    /// 알림 + 데이터
+    #[default]
+    Notification,
+    /// 데이터 전용 (백그라운드/사일런트)
+    DataOnly,
 }
 
 /// FCM 메시지 내용
@@ -17,27 +39,9 @@ pub enum MessageType {
 pub struct FcmMessage {
     pub title: String,
     pub body: String,
-}
-
-/// Android 알림 설정
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
-#[serde(rename_all = "camelCase")]
-pub struct AndroidSettings {
-    /// 우선순위: "high" 또는 "normal"
-    pub priority: String,
-    /// 알림 채널 ID
-    pub channel_id: String,
-}
-
-/// APNs (iOS) 알림 설정
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
-#[serde(rename_all = "camelCase")]
-pub struct ApnsSettings {
-    /// 우선순위: "10" (high) 또는 "5" (normal)
-    pub priority: String,
-    /// 사운드: "default" 또는 커스텀 사운드 파일명
+    /// 모든 플랫폼 공통 알림 이미지 URL (https)
     #[serde(default)]
-    pub sound: Option<String>,
+    pub image: Option<String>,
 }
 
 /// FCM 발송 요청
@@ -45,41 +49,228 @@ pub struct ApnsSettings {
 #[serde(rename_all = "camelCase")]
 pub struct SendRequest {
     pub message_type: MessageType,
-    pub message: FcmMessage,
+    /// 발송 모드
+    #[serde(default)]
+    pub send_mode: SendMode,
+    /// 알림 내용 (데이터 전용 발송 시 생략 가능)
+    #[serde(default)]
+    pub message: Option<FcmMessage>,
     /// 단일 디바이스용 토큰
     #[serde(default)]
     pub token: Option<String>,
+    /// 멀티캐스트용 토큰 목록
+    #[serde(default)]
+    pub tokens: Vec<String>,
     /// 토픽명
     #[serde(default)]
     pub topic: Option<String>,
+    /// 토픽 조건식 (예: `'a' in topics && 'b' in topics`)
+    #[serde(default)]
+    pub condition: Option<String>,
     /// Android 설정
     #[serde(default)]
     pub android: Option<AndroidSettings>,
     /// APNs (iOS) 설정
     #[serde(default)]
     pub apns: Option<ApnsSettings>,
+    /// Web Push 설정
+    #[serde(default)]
+    pub webpush: Option<WebpushSettings>,
+    /// 커스텀 데이터 페이로드 (key-value)
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+    /// FCM 분석 라벨 (fcm_options.analytics_label)
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+    /// 템플릿으로 발송할 때 템플릿 ID와 변수 값 (발송 전에 제목/내용/데이터를 렌더링)
+    #[serde(default)]
+    pub template: Option<TemplateInput>,
+    /// 실제 발송 없이 FCM 검증만 수행 (dry-run)
+    #[serde(default)]
+    pub validate_only: bool,
+}
+
+/// 멀티캐스트 토큰별 발송 결과
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenResult {
+    pub token: String,
+    /// FCM이 요청을 받아들였는지 (validate_only 요청이면 검증 통과)
+    pub success: bool,
+    /// 성공 시 FCM Message ID
+    #[serde(default)]
+    pub message_id: Option<String>,
+    /// 실패 시 에러 메시지
+    #[serde(default)]
+    pub error: Option<String>,
+    /// 실패 시 FCM 에러 코드
+    #[serde(default)]
+    pub error_code: Option<FcmErrorCode>,
+    /// FCM API 호출 시도 횟수
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl TokenResult {
+    pub fn success(token: String, message_id: Option<String>) -> Self {
+        Self {
+            token,
+            success: true,
+            message_id,
+            error: None,
+            error_code: None,
+            attempts: 0,
+        }
+    }
+
+    pub fn failure(token: String, error: FcmSendError) -> Self {
+        Self {
+            token,
+            success: false,
+            message_id: None,
+            error: Some(error.to_string()),
+            error_code: Some(error.code),
+            attempts: 0,
+        }
+    }
+
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+}
+
+/// FCM 발송 결과 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SendStatus {
+    /// 발송됨
+    Sent,
+    /// validate_only 요청이 검증만 통과함 (실제 발송되지 않음)
+    Validated,
+    /// 실패
+    Failed,
 }
 
 /// FCM 발송 결과
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct SendResult {
-    pub success: bool,
+    pub status: SendStatus,
     pub details: String,
+    /// FCM 에러 (FCM API 호출이 실패한 경우)
+    #[serde(default)]
+    pub error: Option<FcmSendError>,
+    /// FCM API 호출 시도 횟수 (멀티캐스트는 토큰별 최대값)
+    #[serde(default)]
+    pub attempts: u32,
+    /// 멀티캐스트 토큰별 결과
+    #[serde(default)]
+    pub token_results: Vec<TokenResult>,
+    /// 발송은 시도했지만 확인이 필요한 사항 (APNs 페이로드 크기 초과 등)
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// 템플릿 렌더링 에러 (렌더링에 실패하면 발송하지 않음)
+    #[serde(default)]
+    pub template_errors: Vec<TemplateRenderError>,
 }
 
 impl SendResult {
     pub fn success(details: String) -> Self {
         Self {
-            success: true,
+            status: SendStatus::Sent,
+            details,
+            error: None,
+            attempts: 0,
+            token_results: Vec::new(),
+            warnings: Vec::new(),
+            template_errors: Vec::new(),
+        }
+    }
+
+    pub fn validated(details: String) -> Self {
+        Self {
+            status: SendStatus::Validated,
             details,
+            error: None,
+            attempts: 0,
+            token_results: Vec::new(),
+            warnings: Vec::new(),
+            template_errors: Vec::new(),
         }
     }
 
     pub fn failure(details: String) -> Self {
         Self {
-            success: false,
+            status: SendStatus::Failed,
             details,
+            error: None,
+            attempts: 0,
+            token_results: Vec::new(),
+            warnings: Vec::new(),
+            template_errors: Vec::new(),
+        }
+    }
+
+    /// 템플릿 렌더링 실패
+    pub fn template_failure(errors: Vec<TemplateRenderError>) -> Self {
+        let details: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        Self {
+            details: details.join("\n"),
+            template_errors: errors,
+            ..Self::failure(String::new())
         }
     }
+
+    /// FCM API 에러로 인한 실패
+    pub fn fcm_failure(error: FcmSendError) -> Self {
+        Self {
+            status: SendStatus::Failed,
+            details: error.to_string(),
+            error: Some(error),
+            attempts: 0,
+            token_results: Vec::new(),
+            warnings: Vec::new(),
+            template_errors: Vec::new(),
+        }
+    }
+
+    /// 멀티캐스트 결과 집계 (모든 토큰이 성공해야 발송/검증 상태)
+    pub fn multicast(token_results: Vec<TokenResult>, validate_only: bool) -> Self {
+        let total = token_results.len();
+        let succeeded = token_results.iter().filter(|r| r.success).count();
+        let status = match (succeeded == total, validate_only) {
+            (false, _) => SendStatus::Failed,
+            (true, false) => SendStatus::Sent,
+            (true, true) => SendStatus::Validated,
+        };
+        let details = if validate_only {
+            format!(
+                "검증 완료 (실제 발송되지 않음): {}개 중 {}개 통과",
+                total, succeeded
+            )
+        } else {
+            format!("멀티캐스트 발송 완료: {}개 중 {}개 성공", total, succeeded)
+        };
+
+        Self {
+            status,
+            details,
+            error: None,
+            attempts: token_results.iter().map(|r| r.attempts).max().unwrap_or(0),
+            token_results,
+            warnings: Vec::new(),
+            template_errors: Vec::new(),
+        }
+    }
+
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
 }
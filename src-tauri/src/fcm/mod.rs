@@ -1,16 +1,41 @@
+pub mod account;
+pub mod android;
+pub mod apns;
 pub mod auth;
+pub mod callback;
 pub mod client;
+pub mod condition;
 pub mod config;
+pub mod error;
 pub mod exchange;
 pub mod history;
+pub mod id_token;
+pub mod lint;
 pub mod message;
+#[cfg(test)]
+mod mock_server;
 pub mod pkce;
+pub mod preview;
+pub mod profile;
+pub mod raw;
+pub mod service_account;
 pub mod template;
+pub mod validate;
+pub mod vault;
+pub mod webpush;
 
 // Re-export commonly used types
+pub use android::*;
+pub use apns::*;
 pub use auth::*;
 pub use client::*;
 pub use config::*;
+pub use error::*;
 pub use history::*;
+pub use lint::*;
 pub use message::*;
+pub use preview::*;
+pub use profile::*;
+pub use raw::*;
 pub use template::*;
+pub use webpush::*;
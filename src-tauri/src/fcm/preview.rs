@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::client::{fcm_endpoint, request_body};
+use super::message::{MessageType, SendRequest};
+use super::template::TemplateRenderError;
+
+/// 토큰을 숨길 때 사용하는 자리 표시자 (curl에서는 환경 변수로 치환됨)
+const TOKEN_PLACEHOLDER: &str = "$FCM_ACCESS_TOKEN";
+
+/// 발송하지 않고 만든 HTTP 요청 미리보기
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SendPreview {
+    pub method: String,
+    pub url: String,
+    /// 요청 헤더 (토큰을 공개하지 않으면 Authorization은 자리 표시자)
+    pub headers: BTreeMap<String, String>,
+    /// 보기 좋게 정렬한 JSON 본문
+    pub body: String,
+    /// 그대로 붙여 넣을 수 있는 curl 명령
+    pub curl: String,
+    /// 실제로 보내는 요청 수 (멀티캐스트는 토큰마다 같은 본문을 보내며 본문은 첫 토큰 기준)
+    pub request_count: u32,
+    /// 템플릿 렌더링 에러 (있으면 나머지 필드는 비어 있음)
+    #[serde(default)]
+    pub template_errors: Vec<TemplateRenderError>,
+}
+
+impl SendPreview {
+    /// 템플릿 렌더링 실패
+    pub fn template_failure(errors: Vec<TemplateRenderError>) -> Self {
+        Self {
+            method: String::new(),
+            url: String::new(),
+            headers: BTreeMap::new(),
+            body: String::new(),
+            curl: String::new(),
+            request_count: 0,
+            template_errors: errors,
+        }
+    }
+}
+
+/// 발송 요청의 최종 HTTP 요청 생성 (`access_token`이 None이면 토큰을 숨김)
+pub fn preview_request(
+    project_id: &str,
+    request: &SendRequest,
+    access_token: Option<&str>,
+) -> Result<SendPreview, String> {
+    let (request, request_count) = match request.message_type {
+        MessageType::Multicast => {
+            let mut tokens: Vec<&str> = Vec::new();
+            for token in request.tokens.iter().map(|t| t.trim()) {
+                if !token.is_empty() && !tokens.contains(&token) {
+                    tokens.push(token);
+                }
+            }
+            let first = tokens
+                .first()
+                .ok_or_else(|| "디바이스 토큰이 없습니다".to_string())?;
+            let single = SendRequest {
+                message_type: MessageType::Single,
+                token: Some(first.to_string()),
+                tokens: Vec::new(),
+                ..request.clone()
+            };
+            (single, tokens.len() as u32)
+        }
+        _ => (request.clone(), 1),
+    };
+
+    let body = serde_json::to_string_pretty(&request_body(&request)?)
+        .map_err(|e| format!("직렬화 실패: {}", e))?;
+    let url = fcm_endpoint(project_id);
+    let authorization = format!("Bearer {}", access_token.unwrap_or(TOKEN_PLACEHOLDER));
+
+    // 자리 표시자는 셸이 환경 변수로 치환하도록 큰따옴표 사용
+    let authorization_arg = match access_token {
+        Some(_) => shell_quote(&format!("Authorization: {}", authorization)),
+        None => format!("\"Authorization: {}\"", authorization),
+    };
+    let curl = format!(
+        "curl -X POST {} \\\n  -H {} \\\n  -H {} \\\n  -d {}",
+        shell_quote(&url),
+        authorization_arg,
+        shell_quote("Content-Type: application/json"),
+        shell_quote(&body)
+    );
+
+    Ok(SendPreview {
+        method: "POST".to_string(),
+        url,
+        headers: BTreeMap::from([
+            ("Authorization".to_string(), authorization),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]),
+        body,
+        curl,
+        request_count,
+        template_errors: Vec::new(),
+    })
+}
+
+/// POSIX 셸용 작은따옴표 인용
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> SendRequest {
+        serde_json::from_value(serde_json::json!({
+            "messageType": "single",
+            "message": { "title": "It's here", "body": "body" },
+            "token": "device-token",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_redacted_preview() {
+        let preview = preview_request("demo", &request(), None).unwrap();
+        assert_eq!(
+            preview.url,
+            "https://fcm.googleapis.com/v1/projects/demo/messages:send"
+        );
+        assert_eq!(preview.headers["Authorization"], "Bearer $FCM_ACCESS_TOKEN");
+        assert!(preview
+            .curl
+            .contains("-H \"Authorization: Bearer $FCM_ACCESS_TOKEN\""));
+        assert!(preview.curl.contains("It'\\''s here"));
+
+        let body: serde_json::Value = serde_json::from_str(&preview.body).unwrap();
+        assert_eq!(body["message"]["token"], "device-token");
+    }
+
+    #[test]
+    fn test_revealed_token() {
+        let preview = preview_request("demo", &request(), Some("ya29.secret")).unwrap();
+        assert_eq!(preview.headers["Authorization"], "Bearer ya29.secret");
+        assert!(preview
+            .curl
+            .contains("-H 'Authorization: Bearer ya29.secret'"));
+    }
+
+    #[test]
+    fn test_multicast_preview() {
+        let mut req = request();
+        req.message_type = MessageType::Multicast;
+        req.token = None;
+        req.tokens = vec!["a".to_string(), " a ".to_string(), "b".to_string()];
+        let preview = preview_request("demo", &req, None).unwrap();
+        assert_eq!(preview.request_count, 2);
+        assert!(preview.body.contains("\"token\": \"a\""));
+    }
+}
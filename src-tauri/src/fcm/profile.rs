@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::config::FcmConfig;
+
+const DEFAULT_PROFILE_NAME: &str = "기본";
+
+/// Firebase 프로젝트 프로필
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    /// 프로필별 OAuth 클라이언트, 토큰 교환 URL, 프로젝트 ID 등
+    pub config: FcmConfig,
+    /// 프로필 활성화 시 전환할 기본 계정 이메일
+    #[serde(default)]
+    pub default_account: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Profile {
+    /// 새 프로필 생성
+    pub fn new(name: String, config: FcmConfig) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            config,
+            default_account: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// 프로필 목록
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileList {
+    /// 활성 프로필 ID
+    pub active_id: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for ProfileList {
+    fn default() -> Self {
+        Self::from_config(FcmConfig::default())
+    }
+}
+
+impl ProfileList {
+    /// 단일 설정으로부터 기본 프로필 목록 생성 (기존 config 마이그레이션용)
+    pub fn from_config(config: FcmConfig) -> Self {
+        let profile = Profile::new(DEFAULT_PROFILE_NAME.to_string(), config);
+        Self {
+            active_id: profile.id.clone(),
+            profiles: vec![profile],
+        }
+    }
+
+    /// 활성 프로필 (활성 ID가 잘못된 경우 첫 번째 프로필)
+    pub fn active(&self) -> &Profile {
+        self.get(&self.active_id)
+            .or_else(|| self.profiles.first())
+            .expect("프로필 목록이 비어 있습니다")
+    }
+
+    /// 활성 프로필 (수정용)
+    pub fn active_mut(&mut self) -> &mut Profile {
+        let index = self
+            .profiles
+            .iter()
+            .position(|p| p.id == self.active_id)
+            .unwrap_or(0);
+        &mut self.profiles[index]
+    }
+
+    /// ID로 프로필 찾기
+    pub fn get(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.id == id)
+    }
+
+    /// 프로필 이름 검증 (비어 있지 않고 중복되지 않아야 함)
+    fn validate_name(&self, name: &str, except_id: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("프로필 이름이 비어 있습니다".to_string());
+        }
+        if self
+            .profiles
+            .iter()
+            .any(|p| p.name == name && Some(p.id.as_str()) != except_id)
+        {
+            return Err(format!("이미 존재하는 프로필 이름입니다: {}", name));
+        }
+        Ok(name.to_string())
+    }
+
+    /// 새 프로필 추가
+    pub fn create(&mut self, name: &str, config: FcmConfig) -> Result<Profile, String> {
+        let name = self.validate_name(name, None)?;
+        let profile = Profile::new(name, config);
+        self.profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    /// 기존 프로필의 설정을 복제하여 새 프로필 추가
+    pub fn clone_profile(&mut self, id: &str, name: &str) -> Result<Profile, String> {
+        let config = self
+            .get(id)
+            .ok_or_else(|| format!("프로필을 찾을 수 없습니다: {}", id))?
+            .config
+            .clone();
+        self.create(name, config)
+    }
+
+    /// 프로필 이름 변경
+    pub fn rename(&mut self, id: &str, name: &str) -> Result<(), String> {
+        let name = self.validate_name(name, Some(id))?;
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("프로필을 찾을 수 없습니다: {}", id))?;
+        profile.name = name;
+        profile.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// 프로필 삭제 (마지막 프로필은 삭제 불가, 활성 프로필 삭제 시 첫 번째 프로필 활성화)
+    pub fn delete(&mut self, id: &str) -> Result<(), String> {
+        if self.get(id).is_none() {
+            return Err(format!("프로필을 찾을 수 없습니다: {}", id));
+        }
+        if self.profiles.len() == 1 {
+            return Err("마지막 프로필은 삭제할 수 없습니다".to_string());
+        }
+
+        self.profiles.retain(|p| p.id != id);
+        if self.active_id == id {
+            self.active_id = self.profiles[0].id.clone();
+        }
+        Ok(())
+    }
+
+    /// 프로필의 기본 계정 지정 (None이면 해제)
+    pub fn set_default_account(&mut self, id: &str, email: Option<String>) -> Result<(), String> {
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("프로필을 찾을 수 없습니다: {}", id))?;
+        profile.default_account = email;
+        profile.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// 이 계정을 기본 계정으로 지정한 프로필의 지정 해제 (변경된 프로필이 있으면 true)
+    pub fn clear_default_account(&mut self, email: &str) -> bool {
+        let mut changed = false;
+        for profile in self
+            .profiles
+            .iter_mut()
+            .filter(|p| p.default_account.as_deref() == Some(email))
+        {
+            profile.default_account = None;
+            profile.updated_at = Utc::now();
+            changed = true;
+        }
+        changed
+    }
+
+    /// 프로필 활성화
+    pub fn activate(&mut self, id: &str) -> Result<(), String> {
+        if self.get(id).is_none() {
+            return Err(format!("프로필을 찾을 수 없습니다: {}", id));
+        }
+        self.active_id = id.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_activate() {
+        let mut list = ProfileList::default();
+        let staging = list.create("staging", FcmConfig::default()).unwrap();
+        assert!(list.create("staging", FcmConfig::default()).is_err());
+        assert!(list.create("  ", FcmConfig::default()).is_err());
+
+        list.activate(&staging.id).unwrap();
+        assert_eq!(list.active().name, "staging");
+        assert!(list.activate("missing").is_err());
+    }
+
+    #[test]
+    fn test_clone_and_rename() {
+        let mut list = ProfileList::default();
+        list.active_mut().config.firebase_project_id = "dev-project".to_string();
+        let source_id = list.active().id.clone();
+
+        let cloned = list.clone_profile(&source_id, "prod").unwrap();
+        assert_eq!(cloned.config.firebase_project_id, "dev-project");
+        assert_ne!(cloned.id, source_id);
+
+        assert!(list.rename(&cloned.id, DEFAULT_PROFILE_NAME).is_err());
+        list.rename(&cloned.id, "production").unwrap();
+        assert_eq!(list.get(&cloned.id).unwrap().name, "production");
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut list = ProfileList::default();
+        let first_id = list.active().id.clone();
+        assert!(list.delete(&first_id).is_err());
+
+        let second = list.create("second", FcmConfig::default()).unwrap();
+        list.activate(&second.id).unwrap();
+        list.delete(&second.id).unwrap();
+        assert_eq!(list.active_id, first_id);
+    }
+}
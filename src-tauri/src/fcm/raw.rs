@@ -0,0 +1,773 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::android::{parse_duration, AndroidSettings};
+use super::apns::{ApnsSettings, CriticalSound, MAX_APNS_PAYLOAD_BYTES};
+use super::client::request_body;
+use super::condition::parse_condition;
+use super::message::{FcmMessage, MessageType, SendMode, SendRequest};
+use super::validate::{validate_data, validate_topic_name};
+use super::webpush::{WebpushNotification, WebpushSettings};
+
+/// FCM 메시지 최대 크기 (대상 필드 제외, 바이트)
+pub const MAX_MESSAGE_BYTES: usize = 4096;
+
+/// 메시지 발송 대상 필드
+const TARGET_FIELDS: &[&str] = &["token", "topic", "condition"];
+
+/// 필드 값의 형식
+#[derive(Clone, Copy)]
+enum Kind {
+    String,
+    Bool,
+    Integer,
+    Number,
+    /// 초 단위 Duration 문자열 (예: "3.5s")
+    Duration,
+    StringList,
+    StringMap,
+    /// 내용을 검사하지 않는 임의의 JSON 객체
+    AnyObject,
+    Object(&'static [Field]),
+}
+
+type Field = (&'static str, Kind);
+
+// FCM HTTP v1 Message 스키마 (필드명은 snake_case, lowerCamelCase 모두 허용)
+// Reference: https://firebase.google.com/docs/reference/fcm/rest/v1/projects.messages
+
+const NOTIFICATION: &[Field] = &[
+    ("title", Kind::String),
+    ("body", Kind::String),
+    ("image", Kind::String),
+];
+
+const FCM_OPTIONS: &[Field] = &[("analytics_label", Kind::String)];
+
+const COLOR: &[Field] = &[
+    ("red", Kind::Number),
+    ("green", Kind::Number),
+    ("blue", Kind::Number),
+    ("alpha", Kind::Number),
+];
+
+const LIGHT_SETTINGS: &[Field] = &[
+    ("color", Kind::Object(COLOR)),
+    ("light_on_duration", Kind::Duration),
+    ("light_off_duration", Kind::Duration),
+];
+
+const ANDROID_NOTIFICATION: &[Field] = &[
+    ("title", Kind::String),
+    ("body", Kind::String),
+    ("icon", Kind::String),
+    ("color", Kind::String),
+    ("sound", Kind::String),
+    ("tag", Kind::String),
+    ("click_action", Kind::String),
+    ("body_loc_key", Kind::String),
+    ("body_loc_args", Kind::StringList),
+    ("title_loc_key", Kind::String),
+    ("title_loc_args", Kind::StringList),
+    ("channel_id", Kind::String),
+    ("ticker", Kind::String),
+    ("sticky", Kind::Bool),
+    ("event_time", Kind::String),
+    ("local_only", Kind::Bool),
+    ("notification_priority", Kind::String),
+    ("default_sound", Kind::Bool),
+    ("default_vibrate_timings", Kind::Bool),
+    ("default_light_settings", Kind::Bool),
+    ("vibrate_timings", Kind::StringList),
+    ("visibility", Kind::String),
+    ("notification_count", Kind::Integer),
+    ("light_settings", Kind::Object(LIGHT_SETTINGS)),
+    ("image", Kind::String),
+    ("proxy", Kind::String),
+];
+
+const ANDROID: &[Field] = &[
+    ("collapse_key", Kind::String),
+    ("priority", Kind::String),
+    ("ttl", Kind::Duration),
+    ("restricted_package_name", Kind::String),
+    ("data", Kind::StringMap),
+    ("notification", Kind::Object(ANDROID_NOTIFICATION)),
+    ("fcm_options", Kind::Object(FCM_OPTIONS)),
+    ("direct_boot_ok", Kind::Bool),
+    ("bandwidth_constrained_ok", Kind::Bool),
+    ("restricted_satellite_ok", Kind::Bool),
+];
+
+const WEBPUSH_FCM_OPTIONS: &[Field] = &[("link", Kind::String), ("analytics_label", Kind::String)];
+
+const WEBPUSH: &[Field] = &[
+    ("headers", Kind::StringMap),
+    ("data", Kind::StringMap),
+    ("notification", Kind::AnyObject),
+    ("fcm_options", Kind::Object(WEBPUSH_FCM_OPTIONS)),
+];
+
+const APNS_FCM_OPTIONS: &[Field] = &[("analytics_label", Kind::String), ("image", Kind::String)];
+
+const APNS: &[Field] = &[
+    ("headers", Kind::StringMap),
+    ("payload", Kind::AnyObject),
+    ("fcm_options", Kind::Object(APNS_FCM_OPTIONS)),
+    ("live_activity_token", Kind::String),
+];
+
+const MESSAGE: &[Field] = &[
+    ("name", Kind::String),
+    ("data", Kind::StringMap),
+    ("notification", Kind::Object(NOTIFICATION)),
+    ("android", Kind::Object(ANDROID)),
+    ("webpush", Kind::Object(WEBPUSH)),
+    ("apns", Kind::Object(APNS)),
+    ("fcm_options", Kind::Object(FCM_OPTIONS)),
+    ("token", Kind::String),
+    ("topic", Kind::String),
+    ("condition", Kind::String),
+];
+
+const DOCUMENT: &[Field] = &[
+    ("validate_only", Kind::Bool),
+    ("message", Kind::Object(MESSAGE)),
+];
+
+/// raw 메시지 검증 문제
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RawIssue {
+    /// 문제 위치 (JSON pointer, 예: "/message/android/ttl")
+    pub path: String,
+    pub message: String,
+}
+
+impl RawIssue {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RawIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// raw 메시지를 구조화된 요청으로 변환한 결과
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RawConversion {
+    pub request: SendRequest,
+    /// 구조화 모드로 옮기지 못한 필드 (JSON pointer)
+    pub dropped: Vec<String>,
+}
+
+/// raw 요청 본문(`{"message": {...}}`) 검증
+///
+/// 알 수 없는 필드, 타입 불일치, 발송 대상 누락, 크기 초과를 JSON pointer 경로와 함께 반환한다.
+pub fn validate_raw_message(document: &Value) -> Vec<RawIssue> {
+    let mut issues = Vec::new();
+    check_value(Kind::Object(DOCUMENT), document, "", &mut issues);
+
+    let Some(message) = lookup(document, "message").and_then(Value::as_object) else {
+        if document.is_object() {
+            issues.push(RawIssue::new("/message", "필수 필드입니다"));
+        }
+        return issues;
+    };
+
+    check_target(message, &mut issues);
+
+    if let Some(data) = lookup_in(message, "data").and_then(string_map) {
+        if let Err(e) = validate_data(&data) {
+            issues.push(RawIssue::new("/message/data", e));
+        }
+    }
+
+    let mut payload = message.clone();
+    payload.retain(|key, _| !TARGET_FIELDS.contains(&key.as_str()));
+    let size = serde_json::to_vec(&payload).map(|v| v.len()).unwrap_or(0);
+    if size > MAX_MESSAGE_BYTES {
+        issues.push(RawIssue::new(
+            "/message",
+            format!(
+                "메시지가 {}바이트로 제한({}바이트)을 넘습니다",
+                size, MAX_MESSAGE_BYTES
+            ),
+        ));
+    }
+
+    if let Some(payload) = lookup_in(message, "apns").and_then(|apns| lookup(apns, "payload")) {
+        let size = serde_json::to_vec(payload).map(|v| v.len()).unwrap_or(0);
+        if size > MAX_APNS_PAYLOAD_BYTES {
+            issues.push(RawIssue::new(
+                "/message/apns/payload",
+                format!(
+                    "APNs 페이로드가 {}바이트로 제한({}바이트)을 넘습니다",
+                    size, MAX_APNS_PAYLOAD_BYTES
+                ),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// 스키마에 따라 값 검사
+fn check_value(kind: Kind, value: &Value, path: &str, issues: &mut Vec<RawIssue>) {
+    let mismatch = |expected: &str| RawIssue::new(path, format!("{}이어야 합니다", expected));
+    match kind {
+        Kind::String if !value.is_string() => issues.push(mismatch("문자열")),
+        Kind::Bool if !value.is_boolean() => issues.push(mismatch("true/false")),
+        Kind::Integer if !(value.is_i64() || value.is_u64()) => issues.push(mismatch("정수")),
+        Kind::Number if !value.is_number() => issues.push(mismatch("숫자")),
+        Kind::Duration => match value.as_str() {
+            Some(s) => {
+                if let Err(e) = parse_duration(s) {
+                    issues.push(RawIssue::new(path, e));
+                }
+            }
+            None => issues.push(mismatch("Duration 문자열 (예: \"3.5s\")")),
+        },
+        Kind::StringList => match value.as_array() {
+            Some(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    check_value(Kind::String, item, &format!("{}/{}", path, i), issues);
+                }
+            }
+            None => issues.push(mismatch("문자열 배열")),
+        },
+        Kind::StringMap => match value.as_object() {
+            Some(map) => {
+                for (key, item) in map {
+                    check_value(Kind::String, item, &child_path(path, key), issues);
+                }
+            }
+            None => issues.push(mismatch("문자열 값을 갖는 객체")),
+        },
+        Kind::AnyObject if !value.is_object() => issues.push(mismatch("객체")),
+        Kind::Object(fields) => match value.as_object() {
+            Some(map) => {
+                for (key, item) in map {
+                    let item_path = child_path(path, key);
+                    match find_field(fields, key) {
+                        Some((_, kind)) => check_value(*kind, item, &item_path, issues),
+                        None => issues.push(RawIssue::new(&item_path, "알 수 없는 필드입니다")),
+                    }
+                }
+            }
+            None => issues.push(mismatch("객체")),
+        },
+        _ => {}
+    }
+}
+
+/// token, topic, condition 중 정확히 하나가 있는지 확인
+fn check_target(message: &Map<String, Value>, issues: &mut Vec<RawIssue>) {
+    let targets: Vec<(&str, &Value)> = TARGET_FIELDS
+        .iter()
+        .filter_map(|name| message.get(*name).map(|value| (*name, value)))
+        .collect();
+
+    match targets.as_slice() {
+        [] => issues.push(RawIssue::new(
+            "/message",
+            "발송 대상(token, topic, condition) 중 하나가 필요합니다",
+        )),
+        [(name, value)] => {
+            let path = format!("/message/{}", name);
+            let Some(value) = value.as_str() else {
+                return;
+            };
+            let result = match *name {
+                _ if value.trim().is_empty() => Err("비어 있습니다".to_string()),
+                "topic" => validate_topic_name(value),
+                "condition" => parse_condition(value).map(|_| ()),
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                issues.push(RawIssue::new(&path, e));
+            }
+        }
+        _ => issues.push(RawIssue::new(
+            "/message",
+            "발송 대상(token, topic, condition)은 하나만 지정할 수 있습니다",
+        )),
+    }
+}
+
+/// 구조화된 요청을 raw 요청 본문으로 변환
+pub fn to_raw(request: &SendRequest) -> Result<Value, String> {
+    request_body(request)
+}
+
+/// raw 요청 본문을 구조화된 요청으로 변환
+///
+/// 구조화 모드에서 표현할 수 없는 필드는 버리고, 다시 raw로 변환했을 때 사라지거나
+/// 달라지는 필드의 경로를 `dropped`로 알려준다.
+pub fn from_raw(document: &Value) -> Result<RawConversion, String> {
+    let message = lookup(document, "message")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "message 객체가 없습니다".to_string())?;
+
+    let target = |name: &str| message.get(name).and_then(Value::as_str).map(String::from);
+    let (token, topic, condition) = (target("token"), target("topic"), target("condition"));
+    let message_type = if token.is_some() {
+        MessageType::Single
+    } else if topic.is_some() {
+        MessageType::Topic
+    } else if condition.is_some() {
+        MessageType::Condition
+    } else {
+        return Err("발송 대상(token, topic, condition)이 없습니다".to_string());
+    };
+
+    let notification = lookup_in(message, "notification").map(|n| FcmMessage {
+        title: str_field(n, "title").unwrap_or_default(),
+        body: str_field(n, "body").unwrap_or_default(),
+        image: str_field(n, "image"),
+    });
+
+    let request = SendRequest {
+        message_type,
+        send_mode: if notification.is_some() {
+            SendMode::Notification
+        } else {
+            SendMode::DataOnly
+        },
+        message: notification,
+        token,
+        tokens: Vec::new(),
+        topic,
+        condition,
+        android: lookup_in(message, "android").and_then(android_from_raw),
+        apns: lookup_in(message, "apns").map(apns_from_raw),
+        webpush: lookup_in(message, "webpush").map(webpush_from_raw),
+        data: lookup_in(message, "data")
+            .and_then(string_map)
+            .unwrap_or_default(),
+        analytics_label: lookup_in(message, "fcm_options")
+            .and_then(|o| str_field(o, "analytics_label")),
+        template: None,
+        validate_only: lookup(document, "validate_only")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    };
+
+    let converted =
+        to_raw(&request).map_err(|e| format!("구조화 모드로 변환할 수 없습니다: {}", e))?;
+    let mut dropped = Vec::new();
+    diff_value(
+        Kind::Object(DOCUMENT),
+        document,
+        &converted,
+        "",
+        &mut dropped,
+    );
+
+    Ok(RawConversion { request, dropped })
+}
+
+/// raw android 설정을 AndroidSettings로 변환 (알림 필드를 평탄화하여 역직렬화)
+fn android_from_raw(android: &Value) -> Option<AndroidSettings> {
+    let mut settings = Map::new();
+    settings.insert("priority".to_string(), json!(""));
+    settings.insert("channelId".to_string(), json!(""));
+
+    let android = android.as_object()?;
+    let notification = lookup_in(android, "notification").and_then(Value::as_object);
+    for (key, value) in android.iter().chain(notification.into_iter().flatten()) {
+        let key = camel_case(key);
+        let value = match key.as_str() {
+            "notification" | "fcmOptions" | "data" => continue,
+            "priority" => json!(value.as_str()?.to_ascii_lowercase()),
+            "lightSettings" => light_settings_from_raw(value)?,
+            _ => value.clone(),
+        };
+        settings.insert(key, value);
+    }
+    if let Some(label) =
+        lookup_in(android, "fcm_options").and_then(|o| lookup(o, "analytics_label"))
+    {
+        settings.insert("analyticsLabel".to_string(), label.clone());
+    }
+
+    serde_json::from_value(Value::Object(settings)).ok()
+}
+
+/// google.type.Color를 #RRGGBBAA로 변환
+fn light_settings_from_raw(value: &Value) -> Option<Value> {
+    let mut light = value.clone();
+    let color = lookup(value, "color")?;
+    let channel = |name: &str, default: f64| {
+        let v = lookup(color, name)
+            .and_then(Value::as_f64)
+            .unwrap_or(default);
+        (v.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+    let hex = format!(
+        "#{:02X}{:02X}{:02X}{:02X}",
+        channel("red", 0.0),
+        channel("green", 0.0),
+        channel("blue", 0.0),
+        channel("alpha", 1.0)
+    );
+    let map = light.as_object_mut()?;
+    map.insert("color".to_string(), json!(hex));
+    let keys: Vec<String> = map.keys().cloned().collect();
+    for key in keys {
+        if let Some(value) = map.remove(&key) {
+            map.insert(camel_case(&key), value);
+        }
+    }
+    Some(light)
+}
+
+/// raw apns 설정을 ApnsSettings로 변환
+fn apns_from_raw(apns: &Value) -> ApnsSettings {
+    let headers = lookup(apns, "headers")
+        .and_then(string_map)
+        .unwrap_or_default();
+    let header = |name: &str| headers.get(name).cloned();
+
+    let empty = Map::new();
+    let payload = lookup(apns, "payload")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let aps = payload
+        .get("aps")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let alert = aps
+        .get("alert")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let flag = |name: &str| aps.get(name).and_then(Value::as_u64).map(|v| v != 0);
+
+    let (sound, critical_sound) = match aps.get("sound") {
+        Some(Value::String(name)) => (Some(name.clone()), None),
+        Some(sound) => (
+            None,
+            Some(CriticalSound {
+                critical: lookup(sound, "critical")
+                    .and_then(Value::as_u64)
+                    .is_some_and(|v| v != 0),
+                name: str_field(sound, "name").unwrap_or_default(),
+                volume: lookup(sound, "volume")
+                    .and_then(Value::as_f64)
+                    .map(|v| v as f32),
+            }),
+        ),
+        None => (None, None),
+    };
+
+    let custom_data = payload
+        .iter()
+        .filter(|(key, _)| key.as_str() != "aps")
+        .map(|(key, value)| {
+            // 문자열은 JSON으로 해석되지 않을 때만 그대로 사용
+            let text = match value {
+                Value::String(s) if serde_json::from_str::<Value>(s).is_err() => s.clone(),
+                _ => value.to_string(),
+            };
+            (key.clone(), text)
+        })
+        .collect();
+
+    let fcm_options = lookup(apns, "fcm_options");
+
+    ApnsSettings {
+        priority: header("apns-priority").unwrap_or_default(),
+        sound,
+        critical_sound,
+        badge: aps
+            .get("badge")
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok()),
+        category: str_field_in(aps, "category"),
+        thread_id: str_field_in(aps, "thread-id"),
+        mutable_content: flag("mutable-content"),
+        content_available: flag("content-available"),
+        interruption_level: aps
+            .get("interruption-level")
+            .and_then(|v| serde_json::from_value(v.clone()).ok()),
+        relevance_score: aps.get("relevance-score").and_then(Value::as_f64),
+        subtitle: str_field_in(alert, "subtitle"),
+        title_loc_key: str_field_in(alert, "title-loc-key"),
+        title_loc_args: string_list(alert.get("title-loc-args")),
+        subtitle_loc_key: str_field_in(alert, "subtitle-loc-key"),
+        subtitle_loc_args: string_list(alert.get("subtitle-loc-args")),
+        loc_key: str_field_in(alert, "loc-key"),
+        loc_args: string_list(alert.get("loc-args")),
+        push_type: header("apns-push-type").and_then(|v| serde_json::from_value(json!(v)).ok()),
+        expiration: header("apns-expiration").and_then(|v| v.parse().ok()),
+        collapse_id: header("apns-collapse-id"),
+        topic: header("apns-topic"),
+        custom_data,
+        image: fcm_options.and_then(|o| str_field(o, "image")),
+        analytics_label: fcm_options.and_then(|o| str_field(o, "analytics_label")),
+    }
+}
+
+/// raw webpush 설정을 WebpushSettings로 변환
+fn webpush_from_raw(webpush: &Value) -> WebpushSettings {
+    let headers = lookup(webpush, "headers")
+        .and_then(string_map)
+        .unwrap_or_default();
+    // Web Push 헤더 이름은 대소문자를 구분하지 않음
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+    };
+    let fcm_options = lookup(webpush, "fcm_options");
+
+    WebpushSettings {
+        ttl: header("TTL").and_then(|v| v.parse().ok()),
+        urgency: header("Urgency").and_then(|v| serde_json::from_value(json!(v)).ok()),
+        topic: header("Topic"),
+        notification: lookup(webpush, "notification")
+            .and_then(|n| serde_json::from_value::<WebpushNotification>(n.clone()).ok()),
+        data: lookup(webpush, "data")
+            .and_then(string_map)
+            .unwrap_or_default(),
+        link: fcm_options.and_then(|o| str_field(o, "link")),
+        analytics_label: fcm_options.and_then(|o| str_field(o, "analytics_label")),
+    }
+}
+
+/// 원본에 있던 값 중 변환 결과에서 사라지거나 달라진 경로 수집
+fn diff_value(kind: Kind, original: &Value, converted: &Value, path: &str, out: &mut Vec<String>) {
+    match (original, converted) {
+        (Value::Object(original), Value::Object(converted)) => {
+            for (key, value) in original {
+                let item_path = child_path(path, key);
+                let (item_kind, converted_value) = match kind {
+                    Kind::Object(fields) => match find_field(fields, key) {
+                        Some((name, item_kind)) => (*item_kind, lookup_in(converted, name)),
+                        None => {
+                            out.push(item_path);
+                            continue;
+                        }
+                    },
+                    _ => (Kind::AnyObject, converted.get(key)),
+                };
+                match converted_value {
+                    Some(converted_value) => {
+                        diff_value(item_kind, value, converted_value, &item_path, out)
+                    }
+                    None => out.push(item_path),
+                }
+            }
+        }
+        (Value::String(a), Value::String(b)) if a.eq_ignore_ascii_case(b) => {}
+        // Color 등 실수 값은 변환 과정의 반올림 오차 허용
+        (Value::Number(a), Value::Number(b))
+            if (a.as_f64().unwrap_or(0.0) - b.as_f64().unwrap_or(0.0)).abs() < 0.005 => {}
+        (a, b) if a == b => {}
+        _ => out.push(path.to_string()),
+    }
+}
+
+fn find_field<'a>(fields: &'a [Field], key: &str) -> Option<&'a Field> {
+    fields
+        .iter()
+        .find(|(name, _)| *name == key || camel_case(name) == key)
+}
+
+/// snake_case 또는 lowerCamelCase 이름으로 필드 조회
+fn lookup<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    value.as_object().and_then(|map| lookup_in(map, name))
+}
+
+fn lookup_in<'a>(map: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    map.get(name).or_else(|| map.get(&camel_case(name)))
+}
+
+fn str_field(value: &Value, name: &str) -> Option<String> {
+    lookup(value, name)
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+fn str_field_in(map: &Map<String, Value>, name: &str) -> Option<String> {
+    map.get(name).and_then(Value::as_str).map(String::from)
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 문자열 값만 모아 BTreeMap으로 변환
+fn string_map(value: &Value) -> Option<BTreeMap<String, String>> {
+    value.as_object().map(|map| {
+        map.iter()
+            .filter_map(|(key, value)| value.as_str().map(|v| (key.clone(), v.to_string())))
+            .collect()
+    })
+}
+
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// JSON pointer 경로에 키 추가 (RFC 6901 이스케이프)
+fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(document: Value) -> Vec<String> {
+        validate_raw_message(&document)
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_message() {
+        let document = json!({
+            "validateOnly": true,
+            "message": {
+                "topic": "news",
+                "notification": { "title": "t", "body": "b" },
+                "android": { "priority": "high", "ttl": "3600s", "notification": { "channelId": "c" } },
+                "apns": { "headers": { "apns-priority": "10" }, "payload": { "aps": { "badge": 1 } } }
+            }
+        });
+        assert!(validate_raw_message(&document).is_empty());
+    }
+
+    #[test]
+    fn test_issue_paths() {
+        assert_eq!(
+            paths(json!({ "message": { "token": "t", "notifcation": {} } })),
+            vec!["/message/notifcation"]
+        );
+        assert_eq!(
+            paths(json!({ "message": { "token": "t", "data": { "count": 1 } } })),
+            vec!["/message/data/count"]
+        );
+        assert_eq!(
+            paths(json!({ "message": { "token": "t", "android": { "ttl": "1h" } } })),
+            vec!["/message/android/ttl"]
+        );
+        assert_eq!(
+            paths(json!({ "message": { "data": {} } })),
+            vec!["/message"]
+        );
+        assert_eq!(
+            paths(json!({ "message": { "token": "t", "topic": "news" } })),
+            vec!["/message"]
+        );
+        assert_eq!(paths(json!({ "validate_only": true })), vec!["/message"]);
+    }
+
+    #[test]
+    fn test_oversized_payload() {
+        let document = json!({
+            "message": { "token": "t", "data": { "blob": "x".repeat(MAX_MESSAGE_BYTES) } }
+        });
+        assert_eq!(paths(document), vec!["/message"]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let document = json!({
+            "message": {
+                "topic": "news",
+                "notification": { "title": "t", "body": "b" },
+                "data": { "deeplink": "app://home" },
+                "android": {
+                    "priority": "HIGH",
+                    "notification": {
+                        "channel_id": "chat",
+                        "light_settings": {
+                            "color": { "red": 1.0, "green": 0.0, "blue": 0.0, "alpha": 1.0 },
+                            "light_on_duration": "1s",
+                            "light_off_duration": "2s"
+                        }
+                    }
+                },
+                "apns": {
+                    "headers": { "apns-priority": "10" },
+                    "payload": { "aps": { "badge": 2, "thread-id": "chat" }, "count": 5 }
+                },
+                "webpush": { "headers": { "TTL": "60" }, "fcm_options": { "link": "https://example.com" } },
+                "fcm_options": { "analytics_label": "launch" }
+            }
+        });
+
+        let conversion = from_raw(&document).unwrap();
+        assert_eq!(conversion.request.message_type, MessageType::Topic);
+        assert_eq!(
+            conversion.request.analytics_label.as_deref(),
+            Some("launch")
+        );
+        assert_eq!(
+            conversion.request.apns.as_ref().unwrap().custom_data["count"],
+            "5"
+        );
+        assert!(conversion.dropped.is_empty(), "{:?}", conversion.dropped);
+    }
+
+    #[test]
+    fn test_dropped_fields() {
+        let document = json!({
+            "message": {
+                "token": "t",
+                "notification": { "title": "t", "body": "b" },
+                "android": { "priority": "high", "notification": { "title": "override" } },
+                "apns": { "live_activity_token": "abc" }
+            }
+        });
+
+        let mut dropped = from_raw(&document).unwrap().dropped;
+        dropped.sort();
+        assert_eq!(
+            dropped,
+            vec![
+                "/message/android/notification/title",
+                "/message/apns/live_activity_token"
+            ]
+        );
+    }
+}
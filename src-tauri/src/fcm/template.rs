@@ -1,40 +1,410 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use super::message::{FcmMessage, SendRequest};
+use super::webpush::WebpushSettings;
+
+/// 템플릿 변수 값 타입
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateVariableType {
+    #[default]
+    Text,
+    /// 숫자로 해석할 수 있는 값
+    Number,
+    /// "true" 또는 "false"
+    Boolean,
+}
+
+/// 템플릿 변수 정의 (`{{name}}`으로 참조)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVariable {
+    /// 변수 이름 (영문/숫자/_)
+    pub name: String,
+    #[serde(default)]
+    pub var_type: TemplateVariableType,
+    /// 값이 주어지지 않았을 때 사용할 기본값
+    #[serde(default)]
+    pub default_value: Option<String>,
+    /// 값과 기본값이 모두 없으면 렌더링 에러
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 템플릿 렌더링 에러 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateErrorKind {
+    /// 필수 변수 값 누락
+    MissingVariable,
+    /// 변수 타입에 맞지 않는 값
+    InvalidValue,
+    /// 닫히지 않은 `{{` 또는 잘못된 변수 이름
+    Syntax,
+}
+
+/// 템플릿 렌더링 에러
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateRenderError {
+    pub kind: TemplateErrorKind,
+    #[serde(default)]
+    pub variable: Option<String>,
+    /// 에러가 발생한 필드 ("title", "body", "data.<key>")
+    #[serde(default)]
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for TemplateRenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{}: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// 템플릿으로 발송할 때 전달하는 값
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInput {
+    pub template_id: String,
+    /// 변수 이름별 값
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// 변수가 치환된 템플릿 내용
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedTemplate {
+    pub title: String,
+    pub body: String,
+    pub data: BTreeMap<String, String>,
+    /// 알림 이미지 URL
+    #[serde(default)]
+    pub image: Option<String>,
+    /// FCM 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+    /// Web Push 설정
+    #[serde(default)]
+    pub webpush: Option<WebpushSettings>,
+}
+
+impl RenderedTemplate {
+    /// 발송 요청에 적용 (요청에 직접 지정한 데이터 키, 이미지, 분석 라벨, Web Push 설정이 우선)
+    pub fn apply_to(self, request: &mut SendRequest) {
+        let image = request
+            .message
+            .as_ref()
+            .and_then(|m| m.image.clone())
+            .or(self.image);
+        request.message = Some(FcmMessage {
+            title: self.title,
+            body: self.body,
+            image,
+        });
+        let mut data = self.data;
+        data.append(&mut request.data);
+        request.data = data;
+        if request.analytics_label.is_none() {
+            request.analytics_label = self.analytics_label;
+        }
+        if request.webpush.is_none() {
+            request.webpush = self.webpush;
+        }
+    }
+}
+
+/// 템플릿 문자열 조각
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// `{{name}}` 자리 표시자 파싱
+///
+/// `\{{`는 치환하지 않고 `{{`로 출력한다. 치환된 값은 다시 해석하지 않는다.
+fn parse_placeholders(text: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            segments.push(Segment::Text(&rest[..start - 1]));
+            segments.push(Segment::Text("{{"));
+            rest = &rest[start + 2..];
+            continue;
+        }
+        segments.push(Segment::Text(&rest[..start]));
+
+        let inner = &rest[start + 2..];
+        let end = inner
+            .find("}}")
+            .ok_or_else(|| "닫히지 않은 {{가 있습니다".to_string())?;
+        let name = inner[..end].trim();
+        if !is_valid_variable_name(name) {
+            return Err(format!("잘못된 변수 이름입니다: {{{{{}}}}}", &inner[..end]));
+        }
+        segments.push(Segment::Variable(name));
+        rest = &inner[end + 2..];
+    }
+    segments.push(Segment::Text(rest));
+
+    Ok(segments)
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
 
 /// 메시지 템플릿
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct Template {
     pub id: String,
+    /// 템플릿이 속한 프로필 ID
+    #[serde(default)]
+    pub profile_id: Option<String>,
     pub name: String,
     pub title: String,
     pub body: String,
+    /// 커스텀 데이터 페이로드
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+    /// 알림 이미지 URL
+    #[serde(default)]
+    pub image: Option<String>,
+    /// FCM 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+    /// Web Push 설정
+    #[serde(default)]
+    pub webpush: Option<WebpushSettings>,
+    /// 제목/내용/데이터 값에서 사용하는 변수 정의
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl Template {
     /// 새 템플릿 생성
-    pub fn new(name: String, title: String, body: String) -> Self {
+    pub fn new(name: String, title: String, body: String, data: BTreeMap<String, String>) -> Self {
         let now = Utc::now();
         Self {
             id: uuid::Uuid::new_v4().to_string(),
+            profile_id: None,
             name,
             title,
             body,
+            data,
+            image: None,
+            analytics_label: None,
+            webpush: None,
+            variables: Vec::new(),
             created_at: now,
             updated_at: now,
         }
     }
 
     /// 템플릿 업데이트
-    pub fn update(&mut self, name: String, title: String, body: String) {
+    pub fn update(
+        &mut self,
+        name: String,
+        title: String,
+        body: String,
+        data: BTreeMap<String, String>,
+    ) {
         self.name = name;
         self.title = title;
         self.body = body;
+        self.data = data;
         self.updated_at = Utc::now();
     }
+
+    /// 변수 정의 검증 (이름 형식, 중복)
+    pub fn validate_variables(&self) -> Result<(), String> {
+        for (i, variable) in self.variables.iter().enumerate() {
+            if !is_valid_variable_name(&variable.name) {
+                return Err(format!(
+                    "변수 이름은 영문/숫자/_만 사용할 수 있습니다: {}",
+                    variable.name
+                ));
+            }
+            if self.variables[..i].iter().any(|v| v.name == variable.name) {
+                return Err(format!("중복된 변수 이름입니다: {}", variable.name));
+            }
+            if let Some(default_value) = &variable.default_value {
+                check_value(variable.var_type, default_value)
+                    .map_err(|e| format!("{} 기본값: {}", variable.name, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 제목, 내용, 데이터 값의 변수를 치환
+    ///
+    /// 정의되지 않은 변수는 값이 주어지면 텍스트로 쓰고, 없으면 필수 변수처럼 에러로 보고한다.
+    pub fn render(
+        &self,
+        values: &BTreeMap<String, String>,
+    ) -> Result<RenderedTemplate, Vec<TemplateRenderError>> {
+        let mut errors = Vec::new();
+
+        let mut fields = vec![
+            ("title".to_string(), self.title.as_str()),
+            ("body".to_string(), self.body.as_str()),
+        ];
+        fields.extend(
+            self.data
+                .iter()
+                .map(|(key, value)| (format!("data.{}", key), value.as_str())),
+        );
+
+        let mut parsed = Vec::with_capacity(fields.len());
+        for (field, text) in &fields {
+            match parse_placeholders(text) {
+                Ok(segments) => parsed.push((field.as_str(), segments)),
+                Err(message) => errors.push(TemplateRenderError {
+                    kind: TemplateErrorKind::Syntax,
+                    variable: None,
+                    field: Some(field.clone()),
+                    message,
+                }),
+            }
+        }
+
+        // 정의된 변수를 먼저 확인하여 사용되지 않은 필수 변수도 보고
+        let mut resolved: BTreeMap<&str, String> = BTreeMap::new();
+        for variable in &self.variables {
+            match self.resolve(&variable.name, values, None) {
+                Ok(value) => {
+                    resolved.insert(&variable.name, value);
+                }
+                Err(error) => errors.push(error),
+            }
+        }
+        for (field, segments) in &parsed {
+            for segment in segments {
+                let Segment::Variable(name) = segment else {
+                    continue;
+                };
+                if resolved.contains_key(name) || self.variable(name).is_some() {
+                    continue;
+                }
+                match self.resolve(name, values, Some(field)) {
+                    Ok(value) => {
+                        resolved.insert(name, value);
+                    }
+                    Err(error) => {
+                        // 같은 변수 에러는 한 번만 보고
+                        if !errors.iter().any(|e| e.variable.as_deref() == Some(*name)) {
+                            errors.push(error);
+                        }
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut rendered: BTreeMap<&str, String> = parsed
+            .into_iter()
+            .map(|(field, segments)| {
+                let text = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => *text,
+                        Segment::Variable(name) => resolved[name].as_str(),
+                    })
+                    .collect();
+                (field, text)
+            })
+            .collect();
+
+        Ok(RenderedTemplate {
+            title: rendered.remove("title").unwrap_or_default(),
+            body: rendered.remove("body").unwrap_or_default(),
+            data: self
+                .data
+                .keys()
+                .map(|key| {
+                    let value = rendered
+                        .remove(format!("data.{}", key).as_str())
+                        .unwrap_or_default();
+                    (key.clone(), value)
+                })
+                .collect(),
+            image: self.image.clone(),
+            analytics_label: self.analytics_label.clone(),
+            webpush: self.webpush.clone(),
+        })
+    }
+
+    fn variable(&self, name: &str) -> Option<&TemplateVariable> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+
+    /// 변수 값 결정 (주어진 값 → 기본값 → 선택 변수는 빈 문자열)
+    fn resolve(
+        &self,
+        name: &str,
+        values: &BTreeMap<String, String>,
+        field: Option<&str>,
+    ) -> Result<String, TemplateRenderError> {
+        let definition = self.variable(name);
+        let value = values
+            .get(name)
+            .filter(|v| !v.is_empty())
+            .or_else(|| definition.and_then(|d| d.default_value.as_ref()));
+
+        let error = |kind, message: String| TemplateRenderError {
+            kind,
+            variable: Some(name.to_string()),
+            field: field.map(String::from),
+            message,
+        };
+
+        match (value, definition) {
+            (Some(value), Some(definition)) => check_value(definition.var_type, value)
+                .map(|_| value.clone())
+                .map_err(|e| error(TemplateErrorKind::InvalidValue, format!("{}: {}", name, e))),
+            (Some(value), None) => Ok(value.clone()),
+            (None, Some(definition)) if !definition.required => Ok(String::new()),
+            (None, _) => Err(error(
+                TemplateErrorKind::MissingVariable,
+                format!("필수 변수 값이 없습니다: {}", name),
+            )),
+        }
+    }
+}
+
+/// 변수 타입에 맞는 값인지 확인
+fn check_value(var_type: TemplateVariableType, value: &str) -> Result<(), String> {
+    match var_type {
+        TemplateVariableType::Text => Ok(()),
+        TemplateVariableType::Number => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|_| ())
+            .ok_or_else(|| format!("숫자여야 합니다 (현재 \"{}\")", value)),
+        TemplateVariableType::Boolean => match value {
+            "true" | "false" => Ok(()),
+            _ => Err(format!("true 또는 false여야 합니다 (현재 \"{}\")", value)),
+        },
+    }
 }
 
 /// 템플릿 목록
@@ -66,3 +436,133 @@ impl TemplateList {
         self.templates.iter().find(|t| t.id == id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(title: &str, variables: serde_json::Value) -> Template {
+        let mut template = Template::new(
+            "promo".to_string(),
+            title.to_string(),
+            "{{count}}개 남았습니다".to_string(),
+            BTreeMap::from([("deeplink".to_string(), "app://item/{{ id }}".to_string())]),
+        );
+        template.variables = serde_json::from_value(variables).unwrap();
+        template
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let t = template(
+            "{{name}}님, \\{{특가}}",
+            serde_json::json!([
+                { "name": "name", "required": true },
+                { "name": "count", "varType": "number", "defaultValue": "3" },
+                { "name": "id" }
+            ]),
+        );
+
+        let rendered = t
+            .render(&values(&[("name", "{{id}}"), ("id", "42")]))
+            .unwrap();
+        assert_eq!(rendered.title, "{{id}}님, {{특가}}");
+        assert_eq!(rendered.body, "3개 남았습니다");
+        assert_eq!(rendered.data["deeplink"], "app://item/42");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let t = template(
+            "{{name}} {{unknown}}",
+            serde_json::json!([
+                { "name": "name", "required": true },
+                { "name": "count", "varType": "number" },
+                { "name": "id", "required": true }
+            ]),
+        );
+
+        let errors = t.render(&values(&[("count", "many")])).unwrap_err();
+        let summary: Vec<(TemplateErrorKind, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.kind, e.variable.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (TemplateErrorKind::MissingVariable, Some("name")),
+                (TemplateErrorKind::InvalidValue, Some("count")),
+                (TemplateErrorKind::MissingVariable, Some("id")),
+                (TemplateErrorKind::MissingVariable, Some("unknown")),
+            ]
+        );
+        assert_eq!(errors[3].field.as_deref(), Some("title"));
+
+        let t = template("{{name", serde_json::json!([]));
+        let errors = t.render(&BTreeMap::new()).unwrap_err();
+        assert_eq!(errors[0].kind, TemplateErrorKind::Syntax);
+    }
+
+    #[test]
+    fn test_validate_variables() {
+        let t = template("", serde_json::json!([{ "name": "a" }, { "name": "a" }]));
+        assert!(t.validate_variables().is_err());
+
+        let t = template(
+            "",
+            serde_json::json!([{ "name": "flag", "varType": "boolean", "defaultValue": "yes" }]),
+        );
+        assert!(t.validate_variables().is_err());
+    }
+
+    #[test]
+    fn test_apply_to_request() {
+        let mut t = template(
+            "{{name}}님",
+            serde_json::json!([{ "name": "name" }, { "name": "count" }, { "name": "id" }]),
+        );
+        t.image = Some("https://example.com/template.png".to_string());
+        t.analytics_label = Some("template_label".to_string());
+        t.webpush = Some(
+            serde_json::from_value(serde_json::json!({ "link": "https://example.com" })).unwrap(),
+        );
+
+        let mut request: SendRequest =
+            serde_json::from_value(serde_json::json!({ "messageType": "topic", "topic": "news" }))
+                .unwrap();
+        let rendered = t
+            .render(&values(&[("name", "홍길동"), ("id", "1")]))
+            .unwrap();
+        rendered.clone().apply_to(&mut request);
+        let message = request.message.as_ref().unwrap();
+        assert_eq!(message.title, "홍길동님");
+        assert_eq!(
+            message.image.as_deref(),
+            Some("https://example.com/template.png")
+        );
+        assert_eq!(request.analytics_label.as_deref(), Some("template_label"));
+        assert!(request.webpush.is_some());
+
+        // 요청에 직접 지정한 값이 우선
+        let mut request: SendRequest = serde_json::from_value(serde_json::json!({
+            "messageType": "topic",
+            "topic": "news",
+            "message": { "title": "", "body": "", "image": "https://example.com/request.png" },
+            "analyticsLabel": "request_label",
+        }))
+        .unwrap();
+        rendered.apply_to(&mut request);
+        assert_eq!(
+            request.message.unwrap().image.as_deref(),
+            Some("https://example.com/request.png")
+        );
+        assert_eq!(request.analytics_label.as_deref(), Some("request_label"));
+    }
+}
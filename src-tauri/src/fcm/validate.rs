@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+/// FCM이 예약한 데이터 키
+/// Reference: https://firebase.google.com/docs/reference/fcm/rest/v1/projects.messages
+const RESERVED_DATA_KEYS: &[&str] = &["from", "message_type"];
+
+/// FCM이 예약한 데이터 키 접두사
+const RESERVED_DATA_KEY_PREFIXES: &[&str] = &["google", "gcm"];
+
+/// 토픽 이름 최대 길이
+const MAX_TOPIC_NAME_LENGTH: usize = 900;
+
+/// 분석 라벨 최대 길이
+const MAX_ANALYTICS_LABEL_LENGTH: usize = 50;
+
+/// 토픽 이름 검증 (`[a-zA-Z0-9-_.~%]{1,900}`)
+pub fn validate_topic_name(topic: &str) -> Result<(), String> {
+    if topic.is_empty() {
+        return Err("토픽 이름이 비어 있습니다".to_string());
+    }
+    if topic.len() > MAX_TOPIC_NAME_LENGTH {
+        return Err(format!(
+            "토픽 이름은 최대 {}자까지 가능합니다",
+            MAX_TOPIC_NAME_LENGTH
+        ));
+    }
+    if let Some(c) = topic
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~' | '%')))
+    {
+        return Err(format!(
+            "토픽 이름에 허용되지 않는 문자가 있습니다: '{}' ({})",
+            c, topic
+        ));
+    }
+    Ok(())
+}
+
+/// 데이터 페이로드 키 검증
+pub fn validate_data(data: &BTreeMap<String, String>) -> Result<(), String> {
+    for key in data.keys() {
+        if key.trim().is_empty() {
+            return Err("데이터 키가 비어 있습니다".to_string());
+        }
+
+        let lower = key.to_ascii_lowercase();
+        if RESERVED_DATA_KEYS.contains(&lower.as_str()) {
+            return Err(format!("예약된 데이터 키는 사용할 수 없습니다: {}", key));
+        }
+        if RESERVED_DATA_KEY_PREFIXES
+            .iter()
+            .any(|prefix| lower.starts_with(prefix))
+        {
+            return Err(format!(
+                "google/gcm으로 시작하는 데이터 키는 사용할 수 없습니다: {}",
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// https URL인지 확인
+pub fn validate_https_url(value: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(value).map_err(|e| format!("URL 파싱 실패: {}", e))?;
+    if url.scheme() != "https" {
+        return Err(format!("https URL이어야 합니다: {}", value));
+    }
+    Ok(())
+}
+
+/// 분석 라벨 검증 (`[a-zA-Z0-9-_.~%]{1,50}`)
+pub fn validate_analytics_label(label: &str) -> Result<(), String> {
+    if label.is_empty() || label.len() > MAX_ANALYTICS_LABEL_LENGTH {
+        return Err(format!(
+            "분석 라벨은 1~{}자여야 합니다",
+            MAX_ANALYTICS_LABEL_LENGTH
+        ));
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~' | '%')))
+    {
+        return Err(format!(
+            "분석 라벨에 허용되지 않는 문자가 있습니다: '{}' ({})",
+            c, label
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(keys: &[&str]) -> BTreeMap<String, String> {
+        keys.iter()
+            .map(|k| (k.to_string(), "value".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_topic_names() {
+        assert!(validate_topic_name("news-kr_v1.0~%").is_ok());
+        assert!(validate_topic_name("").is_err());
+        assert!(validate_topic_name("/topics/news").is_err());
+        assert!(validate_topic_name("뉴스").is_err());
+    }
+
+    #[test]
+    fn test_valid_data_keys() {
+        assert!(validate_data(&data(&["deeplink", "campaign_id"])).is_ok());
+        assert!(validate_data(&BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_reserved_data_keys() {
+        assert!(validate_data(&data(&["from"])).is_err());
+        assert!(validate_data(&data(&["message_type"])).is_err());
+        assert!(validate_data(&data(&["gcm.notification.title"])).is_err());
+        assert!(validate_data(&data(&["google.c.a.e"])).is_err());
+        assert!(validate_data(&data(&[" "])).is_err());
+    }
+
+    #[test]
+    fn test_analytics_labels() {
+        assert!(validate_analytics_label("campaign_2024-05.v1~%20").is_ok());
+        assert!(validate_analytics_label(&"a".repeat(50)).is_ok());
+        assert!(validate_analytics_label(&"a".repeat(51)).is_err());
+        assert!(validate_analytics_label("").is_err());
+        assert!(validate_analytics_label("spring sale").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+
+const VAULT_VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// 토큰 저장 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenBackend {
+    /// 암호화된 볼트 파일
+    #[default]
+    Vault,
+    /// 평문 token.json (테스트/디버깅용)
+    Plaintext,
+}
+
+/// 볼트 암호화 키의 출처
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultKeySource {
+    /// 앱 데이터 디렉터리의 기기 전용 비밀 파일 (자동 잠금 해제)
+    #[default]
+    Machine,
+    /// 사용자 패스프레이즈 (실행할 때마다 잠금 해제 필요)
+    Passphrase,
+}
+
+/// 토큰 저장소 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenStorageSettings {
+    #[serde(default)]
+    pub backend: TokenBackend,
+    #[serde(default)]
+    pub key_source: VaultKeySource,
+}
+
+/// 토큰 저장소 상태
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenStorageStatus {
+    pub settings: TokenStorageSettings,
+    /// 볼트 잠금 해제 여부 (평문 저장소나 기기 키 방식이면 항상 true)
+    pub unlocked: bool,
+    pub vault_exists: bool,
+}
+
+/// 볼트 파일 형식
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultFile {
+    version: u32,
+    key_source: VaultKeySource,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl VaultFile {
+    fn read(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            std::fs::read_to_string(path).map_err(|e| format!("볼트 파일 읽기 실패: {}", e))?;
+        let file: Self =
+            serde_json::from_str(&json).map_err(|e| format!("볼트 파일 파싱 실패: {}", e))?;
+        if file.version != VAULT_VERSION {
+            return Err(format!("지원하지 않는 볼트 버전입니다: {}", file.version));
+        }
+        Ok(Some(file))
+    }
+}
+
+/// 잠금 해제된 볼트 키
+#[derive(Clone)]
+pub struct VaultKey {
+    key: [u8; KEY_LEN],
+    salt: [u8; SALT_LEN],
+    source: VaultKeySource,
+}
+
+impl VaultKey {
+    /// 새 salt로 키 생성 (새 볼트 또는 키 변경 시)
+    pub fn create(secret: &[u8], source: VaultKeySource) -> Result<Self, String> {
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::derive(secret, salt, source)
+    }
+
+    /// 기존 볼트 파일의 salt로 키를 유도하고 복호화로 검증 (파일이 없으면 새 키 생성)
+    pub fn unlock(path: &Path, secret: &[u8], source: VaultKeySource) -> Result<Self, String> {
+        let Some(file) = VaultFile::read(path)? else {
+            return Self::create(secret, source);
+        };
+        if file.key_source != source {
+            return Err("볼트의 키 방식이 설정과 다릅니다".to_string());
+        }
+
+        let salt: [u8; SALT_LEN] = decode_fixed(&file.salt, "salt")?;
+        let key = Self::derive(secret, salt, source)?;
+        key.decrypt(&file).map_err(|_| match source {
+            VaultKeySource::Passphrase => "패스프레이즈가 올바르지 않습니다".to_string(),
+            VaultKeySource::Machine => "기기 키로 볼트를 복호화할 수 없습니다".to_string(),
+        })?;
+        Ok(key)
+    }
+
+    pub fn source(&self) -> VaultKeySource {
+        self.source
+    }
+
+    /// Argon2id로 키 유도
+    fn derive(secret: &[u8], salt: [u8; SALT_LEN], source: VaultKeySource) -> Result<Self, String> {
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(secret, &salt, &mut key)
+            .map_err(|e| format!("키 유도 실패: {}", e))?;
+        Ok(Self { key, salt, source })
+    }
+
+    fn decrypt(&self, file: &VaultFile) -> Result<Vec<u8>, String> {
+        let nonce: [u8; NONCE_LEN] = decode_fixed(&file.nonce, "nonce")?;
+        let ciphertext = STANDARD
+            .decode(&file.ciphertext)
+            .map_err(|e| format!("볼트 데이터 디코딩 실패: {}", e))?;
+
+        XChaCha20Poly1305::new(&self.key.into())
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| "볼트 복호화 실패".to_string())
+    }
+
+    /// 볼트 파일 복호화 (파일이 없으면 None)
+    pub fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<Option<T>, String> {
+        let Some(file) = VaultFile::read(path)? else {
+            return Ok(None);
+        };
+        let plaintext = self.decrypt(&file)?;
+        serde_json::from_slice(&plaintext)
+            .map(Some)
+            .map_err(|e| format!("볼트 데이터 파싱 실패: {}", e))
+    }
+
+    /// 값을 암호화하여 볼트 파일에 저장 (임시 파일에 쓴 뒤 교체)
+    pub fn write<T: Serialize>(&self, path: &Path, value: &T) -> Result<(), String> {
+        let plaintext = serde_json::to_vec(value).map_err(|e| format!("직렬화 실패: {}", e))?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(&self.key.into())
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| "볼트 암호화 실패".to_string())?;
+
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            key_source: self.source,
+            salt: STANDARD.encode(self.salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+        let json = serde_json::to_string(&file).map_err(|e| format!("직렬화 실패: {}", e))?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("디렉터리 생성 실패: {}", e))?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        write_private(&tmp_path, json.as_bytes())?;
+        std::fs::rename(&tmp_path, path).map_err(|e| format!("볼트 파일 저장 실패: {}", e))
+    }
+}
+
+/// 기기 전용 비밀 로드 (없으면 무작위로 생성하여 저장)
+pub fn load_machine_secret(path: &Path) -> Result<Vec<u8>, String> {
+    if path.exists() {
+        let encoded =
+            std::fs::read_to_string(path).map_err(|e| format!("기기 키 읽기 실패: {}", e))?;
+        return STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("기기 키 디코딩 실패: {}", e));
+    }
+
+    let mut secret = vec![0u8; KEY_LEN];
+    rand::thread_rng().fill_bytes(&mut secret);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("디렉터리 생성 실패: {}", e))?;
+    }
+    write_private(path, STANDARD.encode(&secret).as_bytes())?;
+    Ok(secret)
+}
+
+/// 소유자만 읽을 수 있는 권한으로 파일 쓰기
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|e| format!("파일 쓰기 실패 ({}): {}", path.display(), e))
+}
+
+fn decode_fixed<const N: usize>(encoded: &str, name: &str) -> Result<[u8; N], String> {
+    STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("볼트 {} 값이 올바르지 않습니다", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fcm-vault-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_passphrase_roundtrip() {
+        let path = temp_dir().join("token.vault");
+        let value = BTreeMap::from([("refresh".to_string(), "secret".to_string())]);
+
+        let key = VaultKey::unlock(&path, b"correct horse", VaultKeySource::Passphrase).unwrap();
+        key.write(&path, &value).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret"));
+
+        let key = VaultKey::unlock(&path, b"correct horse", VaultKeySource::Passphrase).unwrap();
+        assert_eq!(
+            key.read::<BTreeMap<String, String>>(&path).unwrap(),
+            Some(value)
+        );
+
+        assert!(VaultKey::unlock(&path, b"wrong", VaultKeySource::Passphrase).is_err());
+        assert!(VaultKey::unlock(&path, b"correct horse", VaultKeySource::Machine).is_err());
+    }
+
+    #[test]
+    fn test_machine_secret_is_reused() {
+        let path = temp_dir().join("vault.key");
+        let first = load_machine_secret(&path).unwrap();
+        assert_eq!(first.len(), KEY_LEN);
+        assert_eq!(load_machine_secret(&path).unwrap(), first);
+    }
+}
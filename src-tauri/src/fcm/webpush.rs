@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::validate::{validate_analytics_label, validate_https_url};
+
+/// Web Push Topic 헤더 최대 길이
+const MAX_TOPIC_LENGTH: usize = 32;
+
+/// Web Push 긴급도 (Urgency 헤더)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebpushUrgency {
+    VeryLow,
+    Low,
+    Normal,
+    High,
+}
+
+impl WebpushUrgency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::VeryLow => "very-low",
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+}
+
+/// 알림 텍스트 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum WebpushDirection {
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+/// 알림 액션 버튼
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebpushAction {
+    pub action: String,
+    pub title: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+/// Web Notification 옵션
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebpushNotification {
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub badge: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub actions: Vec<WebpushAction>,
+    /// 사용자가 닫을 때까지 알림 유지
+    #[serde(default)]
+    pub require_interaction: Option<bool>,
+    /// 같은 태그의 알림은 교체됨
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// 같은 태그로 교체될 때 다시 알림 (tag 필요)
+    #[serde(default)]
+    pub renotify: Option<bool>,
+    /// 진동 패턴 (ms)
+    #[serde(default)]
+    pub vibrate: Vec<u32>,
+    #[serde(default)]
+    pub dir: Option<WebpushDirection>,
+    /// 언어 태그 (예: "ko-KR")
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+/// Web Push 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebpushSettings {
+    /// 푸시 서비스 보관 시간 (초, TTL 헤더)
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub urgency: Option<WebpushUrgency>,
+    /// 같은 토픽의 대기 중인 메시지를 교체 (Topic 헤더, 최대 32자)
+    #[serde(default)]
+    pub topic: Option<String>,
+    #[serde(default)]
+    pub notification: Option<WebpushNotification>,
+    /// 웹 전용 데이터 (최상위 data와 합쳐짐)
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+    /// 알림 클릭 시 열 URL (https)
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Web Push 전용 분석 라벨
+    #[serde(default)]
+    pub analytics_label: Option<String>,
+}
+
+/// Web Push 설정 (FCM API용)
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WebpushConfig {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notification: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    data: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fcm_options: Option<WebpushFcmOptions>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WebpushFcmOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analytics_label: Option<String>,
+}
+
+impl WebpushSettings {
+    /// 각 필드 형식 검증
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(link) = &self.link {
+            validate_https_url(link).map_err(|e| format!("webpush.link: {}", e))?;
+        }
+        if let Some(label) = &self.analytics_label {
+            validate_analytics_label(label)
+                .map_err(|e| format!("webpush.analyticsLabel: {}", e))?;
+        }
+        if let Some(topic) = &self.topic {
+            if topic.is_empty()
+                || topic.len() > MAX_TOPIC_LENGTH
+                || !topic
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+            {
+                return Err(format!(
+                    "webpush.topic: 최대 {}자의 영문/숫자/-/_만 사용할 수 있습니다",
+                    MAX_TOPIC_LENGTH
+                ));
+            }
+        }
+        if let Some(notification) = &self.notification {
+            if notification.renotify == Some(true) && notification.tag.is_none() {
+                return Err("webpush.notification.renotify: tag가 필요합니다".to_string());
+            }
+            if let Some(i) = notification
+                .actions
+                .iter()
+                .position(|a| a.action.is_empty() || a.title.is_empty())
+            {
+                return Err(format!(
+                    "webpush.notification.actions[{}]: action과 title이 필요합니다",
+                    i
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 검증 후 FCM API용 설정으로 변환 (`include_notification`이 false면 알림 옵션 생략)
+    pub(crate) fn to_config(&self, include_notification: bool) -> Result<WebpushConfig, String> {
+        self.validate()?;
+
+        let mut headers = BTreeMap::new();
+        if let Some(ttl) = self.ttl {
+            headers.insert("TTL".to_string(), ttl.to_string());
+        }
+        if let Some(urgency) = self.urgency {
+            headers.insert("Urgency".to_string(), urgency.as_str().to_string());
+        }
+        if let Some(topic) = &self.topic {
+            headers.insert("Topic".to_string(), topic.clone());
+        }
+
+        let notification = match &self.notification {
+            Some(notification) if include_notification => {
+                let value = serde_json::to_value(notification)
+                    .map_err(|e| format!("직렬화 실패: {}", e))?;
+                Some(strip_empty(value))
+            }
+            _ => None,
+        };
+
+        Ok(WebpushConfig {
+            headers,
+            notification,
+            data: self.data.clone(),
+            fcm_options: (self.link.is_some() || self.analytics_label.is_some()).then(|| {
+                WebpushFcmOptions {
+                    link: self.link.clone(),
+                    analytics_label: self.analytics_label.clone(),
+                }
+            }),
+        })
+    }
+}
+
+/// null 값과 빈 배열 제거
+fn strip_empty(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map
+            .into_iter()
+            .filter(|(_, v)| !v.is_null() && v.as_array().is_none_or(|a| !a.is_empty()))
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_must_be_https() {
+        let mut settings = WebpushSettings {
+            link: Some("http://example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+
+        settings.link = Some("https://example.com/inbox".to_string());
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_payload() {
+        let settings = WebpushSettings {
+            ttl: Some(3600),
+            urgency: Some(WebpushUrgency::VeryLow),
+            notification: Some(WebpushNotification {
+                icon: Some("/icon.png".to_string()),
+                require_interaction: Some(true),
+                tag: Some("chat".to_string()),
+                renotify: Some(true),
+                ..Default::default()
+            }),
+            link: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(settings.to_config(true).unwrap()).unwrap();
+        assert_eq!(json["headers"]["TTL"], "3600");
+        assert_eq!(json["headers"]["Urgency"], "very-low");
+        assert_eq!(json["notification"]["requireInteraction"], true);
+        assert!(json["notification"].get("badge").is_none());
+        assert!(json["notification"].get("actions").is_none());
+        assert_eq!(json["fcm_options"]["link"], "https://example.com");
+
+        let json = serde_json::to_value(settings.to_config(false).unwrap()).unwrap();
+        assert!(json.get("notification").is_none());
+    }
+
+    #[test]
+    fn test_renotify_requires_tag() {
+        let settings = WebpushSettings {
+            notification: Some(WebpushNotification {
+                renotify: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
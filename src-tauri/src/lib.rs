@@ -3,7 +3,8 @@ pub mod fcm;
 
 #[cfg(debug_assertions)]
 use specta_typescript::Typescript;
-use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
 use tauri::Manager;
 use tauri_specta::{collect_commands, collect_events};
 
@@ -12,7 +13,12 @@ pub mod modules {
     pub mod types;
 }
 
-pub struct AppState {}
+pub struct AppState {
+    /// 잠금 해제된 토큰 볼트 키
+    pub vault_key: Option<fcm::vault::VaultKey>,
+    /// 진행 중인 OAuth 인증의 취소 플래그
+    pub oauth_cancel: Option<Arc<AtomicBool>>,
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -21,13 +27,39 @@ pub fn run() {
             // 설정
             command::get_config,
             command::save_config,
+            // 프로필
+            command::list_profiles,
+            command::create_profile,
+            command::clone_profile,
+            command::rename_profile,
+            command::delete_profile,
+            command::activate_profile,
+            command::set_profile_default_account,
+            // 토큰 저장소
+            command::get_token_storage,
+            command::set_token_storage,
+            command::unlock_vault,
+            command::lock_vault,
             // 인증
             command::is_authenticated,
             command::get_token,
             command::start_oauth,
+            command::cancel_oauth,
+            command::start_service_account_auth,
             command::logout,
+            // 계정
+            command::list_accounts,
+            command::get_account_info,
+            command::switch_account,
+            command::sign_out_account,
             // FCM 발송
             command::send_fcm_message,
+            command::lint_send_request,
+            command::preview_send_request,
+            command::send_raw_fcm_message,
+            command::validate_raw_fcm_message,
+            command::convert_to_raw_message,
+            command::convert_from_raw_message,
             // 템플릿
             command::get_templates,
             command::save_template,
@@ -36,7 +68,7 @@ pub fn run() {
             command::get_history,
             command::clear_history,
         ])
-        .events(collect_events![]);
+        .events(collect_events![modules::types::OAuthProgressEvent]);
 
     #[cfg(debug_assertions)]
     {
@@ -52,7 +84,10 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_os::init())
-        .manage(Mutex::new(AppState {}))
+        .manage(Mutex::new(AppState {
+            vault_key: None,
+            oauth_cancel: None,
+        }))
         .setup(move |app| {
             builder.mount_events(app);
             if let Ok(app_data_dir) = app.path().app_data_dir() {
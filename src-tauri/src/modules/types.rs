@@ -1 +1,11 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
 
+use crate::fcm::auth::OAuthStage;
+
+/// OAuth 인증 진행 이벤트
+#[derive(Debug, Clone, Serialize, Deserialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthProgressEvent {
+    pub stage: OAuthStage,
+}
@@ -5,6 +5,9 @@
 
 
 export const commands = {
+/**
+ * 활성 프로필의 설정 조회
+ */
 async getConfig() : Promise<Result<FcmConfig, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_config") };
@@ -13,6 +16,9 @@ async getConfig() : Promise<Result<FcmConfig, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 활성 프로필의 설정 저장
+ */
 async saveConfig(config: FcmConfig) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("save_config", { config }) };
@@ -21,6 +27,111 @@ async saveConfig(config: FcmConfig) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async listProfiles() : Promise<Result<ProfileList, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_profiles") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createProfile(name: string) : Promise<Result<Profile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cloneProfile(id: string, name: string) : Promise<Result<Profile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clone_profile", { id, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async renameProfile(id: string, name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_profile", { id, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteProfile(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 프로필 활성화 (기본 계정이 지정되어 있고 로그인된 상태면 해당 계정으로 전환)
+ */
+async activateProfile(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("activate_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 프로필의 기본 계정 지정 (None이면 해제)
+ */
+async setProfileDefaultAccount(id: string, email: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_profile_default_account", { id, email }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTokenStorage() : Promise<Result<TokenStorageStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_token_storage") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 토큰 저장 방식 변경 (기존 계정을 새 저장소로 옮김)
+ * 
+ * 패스프레이즈 방식 볼트로 바꾸거나 패스프레이즈를 변경할 때는 새 패스프레이즈를 전달한다.
+ */
+async setTokenStorage(settings: TokenStorageSettings, passphrase: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_token_storage", { settings, passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 패스프레이즈로 볼트 잠금 해제 (볼트가 없으면 이 패스프레이즈로 새로 생성)
+ */
+async unlockVault(passphrase: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_vault", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 메모리의 볼트 키 삭제
+ */
+async lockVault() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_vault") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async isAuthenticated() : Promise<Result<boolean, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("is_authenticated") };
@@ -45,7 +156,29 @@ async startOauth() : Promise<Result<AuthResult, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async logout() : Promise<Result<null, string>> {
+/**
+ * 진행 중인 OAuth 인증 취소 (진행 중인 인증이 없으면 false)
+ */
+async cancelOauth() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_oauth") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async startServiceAccountAuth(keyPath: string) : Promise<Result<AuthResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_service_account_auth", { keyPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 활성 계정 로그아웃
+ */
+async logout() : Promise<Result<LogoutResult, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("logout") };
 } catch (e) {
@@ -53,6 +186,41 @@ async logout() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async listAccounts() : Promise<Result<AccountSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_accounts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 활성 계정의 id_token 클레임 조회 (id_token이 없는 계정이면 None)
+ */
+async getAccountInfo() : Promise<Result<AccountInfo | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_account_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async switchAccount(email: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_account", { email }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async signOutAccount(email: string) : Promise<Result<LogoutResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sign_out_account", { email }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async sendFcmMessage(request: SendRequest) : Promise<Result<SendResult, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("send_fcm_message", { request }) };
@@ -61,6 +229,72 @@ async sendFcmMessage(request: SendRequest) : Promise<Result<SendResult, string>>
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 발송 전 로컬 검사 (페이로드 크기, 대상/데이터 키 형식, 우선순위 조합)
+ * 
+ * 템플릿 발송 요청이면 템플릿을 적용한 최종 요청을 검사한다.
+ * 렌더링에 실패하면 변수별 에러를 `template_errors`에 담아 반환한다.
+ */
+async lintSendRequest(request: SendRequest) : Promise<Result<LintReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lint_send_request", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 발송하지 않고 최종 HTTP 요청 미리보기 (`reveal_token`이 true면 실제 토큰 포함)
+ * 
+ * 템플릿 렌더링에 실패하면 변수별 에러를 `template_errors`에 담아 반환한다.
+ */
+async previewSendRequest(request: SendRequest, revealToken: boolean) : Promise<Result<SendPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_send_request", { request, revealToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * raw 요청 본문(`{"message": {...}}`)을 검증 후 그대로 발송
+ */
+async sendRawFcmMessage(document: string) : Promise<Result<SendResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_raw_fcm_message", { document }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * raw 요청 본문 검증 (JSON 파싱 실패도 문제로 반환)
+ */
+async validateRawFcmMessage(document: string) : Promise<RawIssue[]> {
+    return await TAURI_INVOKE("validate_raw_fcm_message", { document });
+},
+/**
+ * 구조화된 요청을 raw 요청 본문(JSON 문자열)으로 변환
+ */
+async convertToRawMessage(request: SendRequest) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("convert_to_raw_message", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * raw 요청 본문을 구조화된 요청으로 변환
+ */
+async convertFromRawMessage(document: string) : Promise<Result<RawConversion, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("convert_from_raw_message", { document }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getTemplates() : Promise<Result<TemplateList, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_templates") };
@@ -106,6 +340,11 @@ async clearHistory() : Promise<Result<null, string>> {
 /** user-defined events **/
 
 
+export const events = __makeEvents__<{
+oAuthProgressEvent: OAuthProgressEvent
+}>({
+oAuthProgressEvent: "o-auth-progress-event"
+})
 
 /** user-defined constants **/
 
@@ -113,118 +352,1003 @@ async clearHistory() : Promise<Result<null, string>> {
 
 /** user-defined types **/
 
+/**
+ * 로그인한 계정 정보 (id_token 클레임)
+ */
+export type AccountInfo = { email: string | null; name: string | null; picture: string | null; hd: string | null; 
+/**
+ * id_token 만료 시각
+ */
+exp: string | null }
+/**
+ * 프론트엔드에 노출하는 계정 요약 (토큰 제외)
+ */
+export type AccountSummary = { email: string; profileId: string; active: boolean; 
+/**
+ * 액세스 토큰이 유효하거나 갱신 가능한지 여부
+ */
+authenticated: boolean }
 /**
  * Android 알림 설정
  */
 export type AndroidSettings = { 
 /**
- * 우선순위: "high" 또는 "normal"
+ * 우선순위: "high" 또는 "normal" (비우면 지정하지 않음)
  */
 priority: string; 
 /**
  * 알림 채널 ID
  */
-channelId: string }
+channelId: string; 
+/**
+ * 메시지 보관 기간 (예: "3600s", "3.5s")
+ */
+ttl?: string | null; 
+/**
+ * 같은 키의 메시지는 마지막 것만 전달
+ */
+collapseKey?: string | null; 
+/**
+ * 이 패키지명의 앱에만 전달
+ */
+restrictedPackageName?: string | null; 
+/**
+ * 기기 잠금 해제 전(direct boot 모드)에도 전달
+ */
+directBootOk?: boolean | null; 
+/**
+ * 알림 아이콘 리소스 이름
+ */
+icon?: string | null; 
+/**
+ * 아이콘 색상 (#RRGGBB)
+ */
+color?: string | null; 
+/**
+ * 사운드: "default" 또는 raw 리소스 파일명
+ */
+sound?: string | null; 
+/**
+ * 같은 태그의 알림은 교체됨
+ */
+tag?: string | null; 
+/**
+ * 알림 클릭 시 실행할 액티비티의 intent filter
+ */
+clickAction?: string | null; 
+/**
+ * 알림 이미지 URL (https)
+ */
+image?: string | null; 
+/**
+ * 접근성 서비스용 티커 텍스트
+ */
+ticker?: string | null; 
+/**
+ * true이면 클릭해도 알림이 사라지지 않음
+ */
+sticky?: boolean | null; visibility?: AndroidVisibility | null; notificationPriority?: NotificationPriority | null; 
+/**
+ * 앱 아이콘 배지 숫자
+ */
+notificationCount?: number | null; lightSettings?: LightSettings | null; 
+/**
+ * 진동 패턴 (예: ["0s", "0.5s", "0.2s"])
+ */
+vibrateTimings?: string[]; titleLocKey?: string | null; titleLocArgs?: string[]; bodyLocKey?: string | null; bodyLocArgs?: string[]; 
+/**
+ * 알림에 표시할 이벤트 발생 시각
+ */
+eventTime?: string | null; 
+/**
+ * Android 전용 분석 라벨
+ */
+analyticsLabel?: string | null }
+/**
+ * 잠금 화면 알림 공개 범위
+ */
+export type AndroidVisibility = "PRIVATE" | "PUBLIC" | "SECRET"
+/**
+ * apns-push-type 헤더 값
+ */
+export type ApnsPushType = "alert" | "background" | "voip" | "complication" | "fileprovider" | "mdm" | "location" | "liveactivity"
 /**
  * APNs (iOS) 알림 설정
  */
 export type ApnsSettings = { 
 /**
- * 우선순위: "10" (high) 또는 "5" (normal)
+ * 우선순위: "10" (high) 또는 "5" (normal) (비우면 지정하지 않음)
  */
 priority: string; 
 /**
  * 사운드: "default" 또는 커스텀 사운드 파일명
  */
-sound?: string | null }
+sound?: string | null; 
 /**
- * OAuth 인증 결과
+ * 중요 알림 사운드 (sound와 함께 사용할 수 없음)
  */
-export type AuthResult = { success: boolean; message: string; token?: OAuthToken | null }
+criticalSound?: CriticalSound | null; 
 /**
- * FCM 앱 설정
+ * 앱 아이콘 배지 숫자 (0이면 배지 제거)
  */
-export type FcmConfig = { 
+badge?: number | null; 
 /**
- * Google OAuth 2.0 클라이언트 ID
+ * 알림 카테고리 (액션 버튼 구성)
  */
-oauthClientId: string; 
+category?: string | null; 
 /**
- * OAuth 리다이렉트 URL
+ * 알림 그룹 ID
  */
-oauthRedirectUrl: string; 
+threadId?: string | null; 
 /**
- * 외부 토큰 교환 API URL
+ * Notification Service Extension에서 내용 수정 허용
  */
-exchangeCodeUrl: string; 
+mutableContent?: boolean | null; 
 /**
- * Firebase 프로젝트 ID
+ * 백그라운드에서 앱 깨우기
  */
-firebaseProjectId: string }
+contentAvailable?: boolean | null; interruptionLevel?: InterruptionLevel | null; 
 /**
- * FCM 메시지 내용
+ * 알림 요약 정렬 점수 (0.0 ~ 1.0)
  */
-export type FcmMessage = { title: string; body: string }
+relevanceScore?: number | null; subtitle?: string | null; titleLocKey?: string | null; titleLocArgs?: string[]; subtitleLocKey?: string | null; subtitleLocArgs?: string[]; 
 /**
- * 발송 히스토리 항목
+ * 본문 로컬라이즈 키 (aps.alert.loc-key)
  */
-export type HistoryEntry = { id: string; timestamp: string; 
+locKey?: string | null; locArgs?: string[]; pushType?: ApnsPushType | null; 
 /**
- * "single" or "topic"
+ * 만료 시각 (UNIX 초, 0이면 즉시 전달 실패 시 폐기)
  */
-messageType: string; title: string; body: string; success: boolean; details: string }
+expiration?: number | null; 
 /**
- * 히스토리 목록 (최대 100개)
+ * 같은 ID의 알림은 하나로 합쳐짐 (최대 64바이트)
  */
-export type HistoryList = { entries: HistoryEntry[]; maxSize?: number }
+collapseId?: string | null; 
 /**
- * FCM 메시지 발송 타입
+ * 앱 번들 ID (apns-topic)
  */
-export type MessageType = 
+topic?: string | null; 
 /**
- * 단일 디바이스
+ * aps 외 최상위 커스텀 키 (값은 JSON 리터럴, 파싱할 수 없으면 문자열로 취급)
  */
-"single" | 
+customData?: Partial<{ [key in string]: string }>; 
 /**
- * 토픽
+ * iOS 알림 이미지 URL (https, Notification Service Extension 필요)
  */
-"topic"
+image?: string | null; 
 /**
- * OAuth 토큰 정보
+ * APNs 전용 분석 라벨
  */
-export type OAuthToken = { accessToken: string; refreshToken?: string; idToken?: string; tokenType: string; expiry: string }
+analyticsLabel?: string | null }
 /**
- * FCM 발송 요청
+ * 인증 방식
  */
-export type SendRequest = { messageType: MessageType; message: FcmMessage; 
+export type AuthMode = 
 /**
- * 단일 디바이스용 토큰
+ * 브라우저 OAuth (PKCE)
  */
-token?: string | null; 
+"o_auth" | 
 /**
- * 토픽명
+ * 서비스 계정 JSON 키
  */
-topic?: string | null; 
+"service_account"
 /**
- * Android 설정
+ * OAuth 인증 결과
  */
-android?: AndroidSettings | null; 
+export type AuthResult = { success: boolean; message: string; token?: OAuthToken | null; 
 /**
- * APNs (iOS) 설정
+ * id_token에서 읽은 계정 정보 (서비스 계정 등 id_token이 없으면 None)
  */
-apns?: ApnsSettings | null }
+account?: AccountInfo | null; 
 /**
- * FCM 발송 결과
+ * 인증은 성공했지만 확인이 필요한 사항 (허용되지 않은 도메인 등)
  */
-export type SendResult = { success: boolean; details: string }
+warnings?: string[] }
 /**
- * 메시지 템플릿
+ * 중요 알림 사운드 (aps.sound 딕셔너리)
  */
-export type Template = { id: string; name: string; title: string; body: string; createdAt: string; updatedAt: string }
+export type CriticalSound = { 
 /**
- * 템플릿 목록
+ * true이면 무음 모드에서도 재생 (Critical Alerts 권한 필요)
  */
-export type TemplateList = { templates: Template[] }
+critical: boolean; 
+/**
+ * 사운드 파일명 또는 "default"
+ */
+name: string; 
+/**
+ * 볼륨 (0.0 ~ 1.0)
+ */
+volume?: number | null }
+/**
+ * authorization code 교환 방식
+ */
+export type ExchangeMode = 
+/**
+ * 외부 토큰 교환 API (relay)를 통해 교환
+ */
+"relay" | 
+/**
+ * Google 토큰 엔드포인트에 직접 교환
+ */
+"direct"
+/**
+ * FCM 앱 설정
+ */
+export type FcmConfig = { 
+/**
+ * 인증 방식
+ */
+authMode?: AuthMode; 
+/**
+ * Google OAuth 2.0 클라이언트 ID
+ */
+oauthClientId: string; 
+/**
+ * OAuth 리다이렉트 URL (콜백 서버의 호스트/포트/경로로 사용)
+ */
+oauthRedirectUrl: string; 
+/**
+ * 리다이렉트 URL의 포트 대신 빈 포트를 자동 할당
+ */
+oauthEphemeralPort?: boolean; 
+/**
+ * OAuth 클라이언트 시크릿 (직접 교환 시 사용)
+ */
+oauthClientSecret?: string; 
+/**
+ * authorization code 교환 방식
+ */
+exchangeMode?: ExchangeMode; 
+/**
+ * 외부 토큰 교환 API URL
+ */
+exchangeCodeUrl: string; 
+/**
+ * 외부 토큰 갱신 API URL (비어 있으면 relay 교환 토큰은 만료 후 다시 로그인)
+ */
+refreshTokenUrl?: string; 
+/**
+ * 토큰 엔드포인트 URL (직접 교환 시 사용)
+ */
+tokenUrl?: string; 
+/**
+ * 토큰 취소 엔드포인트 URL (로그아웃 시 사용)
+ */
+revokeUrl?: string; 
+/**
+ * 서비스 계정 JSON 키 파일 경로
+ */
+serviceAccountKeyPath?: string; 
+/**
+ * Firebase 프로젝트 ID
+ */
+firebaseProjectId: string; 
+/**
+ * 발송 재시도 정책
+ */
+retry?: RetryPolicy; 
+/**
+ * 허용할 Google Workspace 도메인 (id_token의 hd, 비어 있으면 검사 안 함)
+ */
+allowedDomains?: string[] }
+/**
+ * FCM 에러 코드
+ * Reference: https://firebase.google.com/docs/reference/fcm/rest/v1/ErrorCode
+ */
+export type FcmErrorCode = 
+/**
+ * 등록 해제되었거나 만료된 토큰
+ */
+"UNREGISTERED" | 
+/**
+ * 잘못된 요청 파라미터
+ */
+"INVALID_ARGUMENT" | 
+/**
+ * 토큰의 발신자 ID와 프로젝트가 일치하지 않음
+ */
+"SENDER_ID_MISMATCH" | 
+/**
+ * 발송 한도 초과
+ */
+"QUOTA_EXCEEDED" | 
+/**
+ * FCM 서버 일시적 사용 불가
+ */
+"UNAVAILABLE" | 
+/**
+ * FCM 내부 오류
+ */
+"INTERNAL" | 
+/**
+ * APNs 인증서 또는 Web Push 인증 키 오류
+ */
+"THIRD_PARTY_AUTH_ERROR" | 
+/**
+ * 액세스 토큰이 유효하지 않음
+ */
+"UNAUTHENTICATED" | 
+/**
+ * 프로젝트 권한 없음
+ */
+"PERMISSION_DENIED" | 
+/**
+ * 네트워크 오류 등으로 응답을 받지 못함
+ */
+"NETWORK" | 
+/**
+ * 알 수 없는 오류
+ */
+"UNKNOWN"
+/**
+ * FCM 메시지 내용
+ */
+export type FcmMessage = { title: string; body: string; 
+/**
+ * 모든 플랫폼 공통 알림 이미지 URL (https)
+ */
+image?: string | null }
+/**
+ * FCM 발송 에러
+ */
+export type FcmSendError = { code: FcmErrorCode; 
+/**
+ * HTTP 상태 코드 (응답을 받지 못한 경우 None)
+ */
+httpStatus?: number | null; 
+/**
+ * google.rpc 상태 (예: "NOT_FOUND")
+ */
+status?: string; message: string; 
+/**
+ * BadRequest 필드 위반 목록
+ */
+fieldViolations?: FieldViolation[]; 
+/**
+ * Retry-After 헤더 값 (초, HTTP-date는 남은 시간으로 변환)
+ */
+retryAfterSecs?: number | null }
+/**
+ * BadRequest 필드 위반 정보
+ */
+export type FieldViolation = { field: string; description: string }
+/**
+ * 발송 히스토리 항목
+ */
+export type HistoryEntry = { id: string; timestamp: string; 
+/**
+ * 발송한 프로필 ID
+ */
+profileId?: string | null; 
+/**
+ * "single", "topic", "condition", "multicast" or "raw"
+ */
+messageType: string; 
+/**
+ * 발송 대상 (토큰, 토픽명, 조건식 또는 토큰 수 요약)
+ */
+target?: string; 
+/**
+ * 발송 모드
+ */
+sendMode?: SendMode; title: string; body: string; 
+/**
+ * 커스텀 데이터 페이로드
+ */
+data?: Partial<{ [key in string]: string }>; 
+/**
+ * 알림 이미지 URL
+ */
+image?: string | null; 
+/**
+ * FCM 분석 라벨
+ */
+analyticsLabel?: string | null; 
+/**
+ * Web Push 설정
+ */
+webpush?: WebpushSettings | null; 
+/**
+ * 발송 결과 상태
+ */
+status: SendStatus; details: string; 
+/**
+ * 실패 시 FCM 에러 코드
+ */
+errorCode?: FcmErrorCode | null; 
+/**
+ * FCM API 호출 시도 횟수
+ */
+attempts?: number; 
+/**
+ * 멀티캐스트 토큰별 결과
+ */
+tokenResults?: TokenResult[]; 
+/**
+ * raw 모드로 발송한 요청 본문 (JSON)
+ */
+raw?: string | null }
+/**
+ * 히스토리 목록 (최대 100개)
+ */
+export type HistoryList = { entries: HistoryEntry[]; maxSize?: number }
+/**
+ * 알림 중단 수준 (iOS 15+)
+ */
+export type InterruptionLevel = "passive" | "active" | "time-sensitive" | "critical"
+/**
+ * LED 설정
+ */
+export type LightSettings = { 
+/**
+ * LED 색상 (#RRGGBB 또는 #RRGGBBAA)
+ */
+color: string; 
+/**
+ * 켜짐 시간 (예: "0.5s")
+ */
+lightOnDuration: string; 
+/**
+ * 꺼짐 시간 (예: "1s")
+ */
+lightOffDuration: string }
+/**
+ * 린트 항목 종류
+ */
+export type LintCode = 
+/**
+ * 발송 대상 누락 또는 형식 오류
+ */
+"invalid_target" | 
+/**
+ * 예약된 데이터 키 사용
+ */
+"reserved_data_key" | 
+/**
+ * 알림 모드인데 제목/내용이 비어 있음
+ */
+"empty_notification" | 
+/**
+ * 플랫폼 설정 형식 오류
+ */
+"invalid_settings" | 
+/**
+ * 페이로드 크기 제한 초과
+ */
+"payload_too_large" | 
+/**
+ * 데이터 전용 메시지의 Android high 우선순위 (FCM이 우선순위를 낮출 수 있음)
+ */
+"data_only_high_priority" | 
+/**
+ * APNs 우선순위/푸시 타입 불일치
+ */
+"apns_priority_mismatch" | 
+/**
+ * 템플릿 렌더링 실패
+ */
+"template_render"
+/**
+ * 린트 결과 항목
+ */
+export type LintIssue = { code: LintCode; 
+/**
+ * 문제가 있는 필드 (예: "topic", "android.priority")
+ */
+field: string; message: string }
+/**
+ * 발송 전 린트 결과
+ */
+export type LintReport = { 
+/**
+ * 발송이 실패하는 문제
+ */
+errors: LintIssue[]; 
+/**
+ * 발송은 되지만 의도와 다를 수 있는 사항
+ */
+warnings: LintIssue[]; 
+/**
+ * 메시지를 만들 수 없으면 None
+ */
+sizes: PayloadSizes | null; 
+/**
+ * 템플릿 렌더링 에러 (있으면 요청 자체는 검사하지 않음)
+ */
+templateErrors?: TemplateRenderError[] }
+/**
+ * 로그아웃 결과
+ */
+export type LogoutResult = { 
+/**
+ * 로그아웃한 계정 (로그인된 계정이 없었으면 None)
+ */
+email: string | null; 
+/**
+ * 모든 토큰 취소 성공 여부 (취소할 토큰이 없으면 true)
+ */
+revoked: boolean; 
+/**
+ * 토큰 취소 실패 메시지 (실패해도 로컬 토큰은 삭제됨)
+ */
+errors?: string[] }
+/**
+ * FCM 메시지 발송 타입
+ */
+export type MessageType = 
+/**
+ * 단일 디바이스
+ */
+"single" | 
+/**
+ * 토픽
+ */
+"topic" | 
+/**
+ * 토픽 조건식
+ */
+"condition" | 
+/**
+ * 여러 디바이스 (멀티캐스트)
+ */
+"multicast"
+/**
+ * 알림 표시 우선순위 (Android 7.1 이하용)
+ */
+export type NotificationPriority = "PRIORITY_MIN" | "PRIORITY_LOW" | "PRIORITY_DEFAULT" | "PRIORITY_HIGH" | "PRIORITY_MAX"
+/**
+ * OAuth 인증 진행 이벤트
+ */
+export type OAuthProgressEvent = { stage: OAuthStage }
+/**
+ * OAuth 인증 진행 단계
+ */
+export type OAuthStage = 
+/**
+ * 브라우저를 열고 콜백 대기 중
+ */
+"browser_opened" | 
+/**
+ * 콜백으로 인증 코드 수신
+ */
+"callback_received" | 
+/**
+ * 인증 코드를 토큰으로 교환 중
+ */
+"exchanging" | 
+/**
+ * 인증 완료
+ */
+"done" | 
+/**
+ * 인증 실패 또는 타임아웃
+ */
+"failed" | 
+/**
+ * 사용자가 취소
+ */
+"cancelled"
+/**
+ * OAuth 토큰 정보
+ */
+export type OAuthToken = { accessToken: string; refreshToken?: string; idToken?: string; tokenType: string; expiry: string }
+/**
+ * 직렬화된 페이로드 크기 (바이트, 대상 필드 제외)
+ */
+export type PayloadSizes = { 
+/**
+ * FCM 메시지 전체
+ */
+message: number; 
+/**
+ * Android 기기에 전달되는 부분
+ */
+android: number; 
+/**
+ * APNs 페이로드 (notification/data 병합 후)
+ */
+apns: number; 
+/**
+ * Web Push 페이로드
+ */
+webpush: number }
+/**
+ * Firebase 프로젝트 프로필
+ */
+export type Profile = { id: string; name: string; 
+/**
+ * 프로필별 OAuth 클라이언트, 토큰 교환 URL, 프로젝트 ID 등
+ */
+config: FcmConfig; 
+/**
+ * 프로필 활성화 시 전환할 기본 계정 이메일
+ */
+defaultAccount?: string | null; createdAt: string; updatedAt: string }
+/**
+ * 프로필 목록
+ */
+export type ProfileList = { 
+/**
+ * 활성 프로필 ID
+ */
+activeId: string; profiles: Profile[] }
+/**
+ * raw 메시지를 구조화된 요청으로 변환한 결과
+ */
+export type RawConversion = { request: SendRequest; 
+/**
+ * 구조화 모드로 옮기지 못한 필드 (JSON pointer)
+ */
+dropped: string[] }
+/**
+ * raw 메시지 검증 문제
+ */
+export type RawIssue = { 
+/**
+ * 문제 위치 (JSON pointer, 예: "/message/android/ttl")
+ */
+path: string; message: string }
+/**
+ * FCM 발송 재시도 정책
+ */
+export type RetryPolicy = { 
+/**
+ * 최대 시도 횟수 (첫 시도 포함, 1이면 재시도 안 함)
+ */
+maxAttempts: number; 
+/**
+ * 첫 재시도 대기 시간 (ms)
+ */
+initialBackoffMs: number; 
+/**
+ * 최대 대기 시간 (ms), Retry-After가 이보다 길면 재시도하지 않고 실패 상세에 남김
+ */
+maxBackoffMs: number }
+/**
+ * FCM 메시지 발송 모드
+ */
+export type SendMode = 
+/**
+ * 알림 + 데이터
+ */
+"notification" | 
+/**
+ * 데이터 전용 (백그라운드/사일런트)
+ */
+"data_only"
+/**
+ * 발송하지 않고 만든 HTTP 요청 미리보기
+ */
+export type SendPreview = { method: string; url: string; 
+/**
+ * 요청 헤더 (토큰을 공개하지 않으면 Authorization은 자리 표시자)
+ */
+headers: Partial<{ [key in string]: string }>; 
+/**
+ * 보기 좋게 정렬한 JSON 본문
+ */
+body: string; 
+/**
+ * 그대로 붙여 넣을 수 있는 curl 명령
+ */
+curl: string; 
+/**
+ * 실제로 보내는 요청 수 (멀티캐스트는 토큰마다 같은 본문을 보내며 본문은 첫 토큰 기준)
+ */
+requestCount: number; 
+/**
+ * 템플릿 렌더링 에러 (있으면 나머지 필드는 비어 있음)
+ */
+templateErrors?: TemplateRenderError[] }
+/**
+ * FCM 발송 요청
+ */
+export type SendRequest = { messageType: MessageType; 
+/**
+ * 발송 모드
+ */
+sendMode?: SendMode; 
+/**
+ * 알림 내용 (데이터 전용 발송 시 생략 가능)
+ */
+message?: FcmMessage | null; 
+/**
+ * 단일 디바이스용 토큰
+ */
+token?: string | null; 
+/**
+ * 멀티캐스트용 토큰 목록
+ */
+tokens?: string[]; 
+/**
+ * 토픽명
+ */
+topic?: string | null; 
+/**
+ * 토픽 조건식 (예: `'a' in topics && 'b' in topics`)
+ */
+condition?: string | null; 
+/**
+ * Android 설정
+ */
+android?: AndroidSettings | null; 
+/**
+ * APNs (iOS) 설정
+ */
+apns?: ApnsSettings | null; 
+/**
+ * Web Push 설정
+ */
+webpush?: WebpushSettings | null; 
+/**
+ * 커스텀 데이터 페이로드 (key-value)
+ */
+data?: Partial<{ [key in string]: string }>; 
+/**
+ * FCM 분석 라벨 (fcm_options.analytics_label)
+ */
+analyticsLabel?: string | null; 
+/**
+ * 템플릿으로 발송할 때 템플릿 ID와 변수 값 (발송 전에 제목/내용/데이터를 렌더링)
+ */
+template?: TemplateInput | null; 
+/**
+ * 실제 발송 없이 FCM 검증만 수행 (dry-run)
+ */
+validateOnly?: boolean }
+/**
+ * FCM 발송 결과
+ */
+export type SendResult = { status: SendStatus; details: string; 
+/**
+ * FCM 에러 (FCM API 호출이 실패한 경우)
+ */
+error?: FcmSendError | null; 
+/**
+ * FCM API 호출 시도 횟수 (멀티캐스트는 토큰별 최대값)
+ */
+attempts?: number; 
+/**
+ * 멀티캐스트 토큰별 결과
+ */
+tokenResults?: TokenResult[]; 
+/**
+ * 발송은 시도했지만 확인이 필요한 사항 (APNs 페이로드 크기 초과 등)
+ */
+warnings?: string[]; 
+/**
+ * 템플릿 렌더링 에러 (렌더링에 실패하면 발송하지 않음)
+ */
+templateErrors?: TemplateRenderError[] }
+/**
+ * FCM 발송 결과 상태
+ */
+export type SendStatus = 
+/**
+ * 발송됨
+ */
+"sent" | 
+/**
+ * validate_only 요청이 검증만 통과함 (실제 발송되지 않음)
+ */
+"validated" | 
+/**
+ * 실패
+ */
+"failed"
+/**
+ * 메시지 템플릿
+ */
+export type Template = { id: string; 
+/**
+ * 템플릿이 속한 프로필 ID
+ */
+profileId?: string | null; name: string; title: string; body: string; 
+/**
+ * 커스텀 데이터 페이로드
+ */
+data?: Partial<{ [key in string]: string }>; 
+/**
+ * 알림 이미지 URL
+ */
+image?: string | null; 
+/**
+ * FCM 분석 라벨
+ */
+analyticsLabel?: string | null; 
+/**
+ * Web Push 설정
+ */
+webpush?: WebpushSettings | null; 
+/**
+ * 제목/내용/데이터 값에서 사용하는 변수 정의
+ */
+variables?: TemplateVariable[]; createdAt: string; updatedAt: string }
+/**
+ * 템플릿 렌더링 에러 종류
+ */
+export type TemplateErrorKind = 
+/**
+ * 필수 변수 값 누락
+ */
+"missing_variable" | 
+/**
+ * 변수 타입에 맞지 않는 값
+ */
+"invalid_value" | 
+/**
+ * 닫히지 않은 `{{` 또는 잘못된 변수 이름
+ */
+"syntax"
+/**
+ * 템플릿으로 발송할 때 전달하는 값
+ */
+export type TemplateInput = { templateId: string; 
+/**
+ * 변수 이름별 값
+ */
+variables?: Partial<{ [key in string]: string }> }
+/**
+ * 템플릿 목록
+ */
+export type TemplateList = { templates: Template[] }
+/**
+ * 템플릿 렌더링 에러
+ */
+export type TemplateRenderError = { kind: TemplateErrorKind; variable?: string | null; 
+/**
+ * 에러가 발생한 필드 ("title", "body", "data.<key>")
+ */
+field?: string | null; message: string }
+/**
+ * 템플릿 변수 정의 (`{{name}}`으로 참조)
+ */
+export type TemplateVariable = { 
+/**
+ * 변수 이름 (영문/숫자/_)
+ */
+name: string; varType?: TemplateVariableType; 
+/**
+ * 값이 주어지지 않았을 때 사용할 기본값
+ */
+defaultValue?: string | null; 
+/**
+ * 값과 기본값이 모두 없으면 렌더링 에러
+ */
+required?: boolean; description?: string | null }
+/**
+ * 템플릿 변수 값 타입
+ */
+export type TemplateVariableType = "text" | 
+/**
+ * 숫자로 해석할 수 있는 값
+ */
+"number" | 
+/**
+ * "true" 또는 "false"
+ */
+"boolean"
+/**
+ * 토큰 저장 방식
+ */
+export type TokenBackend = 
+/**
+ * 암호화된 볼트 파일
+ */
+"vault" | 
+/**
+ * 평문 token.json (테스트/디버깅용)
+ */
+"plaintext"
+/**
+ * 멀티캐스트 토큰별 발송 결과
+ */
+export type TokenResult = { token: string; 
+/**
+ * FCM이 요청을 받아들였는지 (validate_only 요청이면 검증 통과)
+ */
+success: boolean; 
+/**
+ * 성공 시 FCM Message ID
+ */
+messageId?: string | null; 
+/**
+ * 실패 시 에러 메시지
+ */
+error?: string | null; 
+/**
+ * 실패 시 FCM 에러 코드
+ */
+errorCode?: FcmErrorCode | null; 
+/**
+ * FCM API 호출 시도 횟수
+ */
+attempts?: number }
+/**
+ * 토큰 저장소 설정
+ */
+export type TokenStorageSettings = { backend?: TokenBackend; keySource?: VaultKeySource }
+/**
+ * 토큰 저장소 상태
+ */
+export type TokenStorageStatus = { settings: TokenStorageSettings; 
+/**
+ * 볼트 잠금 해제 여부 (평문 저장소나 기기 키 방식이면 항상 true)
+ */
+unlocked: boolean; vaultExists: boolean }
+/**
+ * 볼트 암호화 키의 출처
+ */
+export type VaultKeySource = 
+/**
+ * 앱 데이터 디렉터리의 기기 전용 비밀 파일 (자동 잠금 해제)
+ */
+"machine" | 
+/**
+ * 사용자 패스프레이즈 (실행할 때마다 잠금 해제 필요)
+ */
+"passphrase"
+/**
+ * 알림 액션 버튼
+ */
+export type WebpushAction = { action: string; title: string; icon?: string | null }
+/**
+ * 알림 텍스트 방향
+ */
+export type WebpushDirection = "auto" | "ltr" | "rtl"
+/**
+ * Web Notification 옵션
+ */
+export type WebpushNotification = { icon?: string | null; badge?: string | null; image?: string | null; actions?: WebpushAction[]; 
+/**
+ * 사용자가 닫을 때까지 알림 유지
+ */
+requireInteraction?: boolean | null; 
+/**
+ * 같은 태그의 알림은 교체됨
+ */
+tag?: string | null; 
+/**
+ * 같은 태그로 교체될 때 다시 알림 (tag 필요)
+ */
+renotify?: boolean | null; 
+/**
+ * 진동 패턴 (ms)
+ */
+vibrate?: number[]; dir?: WebpushDirection | null; 
+/**
+ * 언어 태그 (예: "ko-KR")
+ */
+lang?: string | null }
+/**
+ * Web Push 설정
+ */
+export type WebpushSettings = { 
+/**
+ * 푸시 서비스 보관 시간 (초, TTL 헤더)
+ */
+ttl?: number | null; urgency?: WebpushUrgency | null; 
+/**
+ * 같은 토픽의 대기 중인 메시지를 교체 (Topic 헤더, 최대 32자)
+ */
+topic?: string | null; notification?: WebpushNotification | null; 
+/**
+ * 웹 전용 데이터 (최상위 data와 합쳐짐)
+ */
+data?: Partial<{ [key in string]: string }>; 
+/**
+ * 알림 클릭 시 열 URL (https)
+ */
+link?: string | null; 
+/**
+ * Web Push 전용 분석 라벨
+ */
+analyticsLabel?: string | null }
+/**
+ * Web Push 긴급도 (Urgency 헤더)
+ */
+export type WebpushUrgency = "very-low" | "low" | "normal" | "high"
 
 /** tauri-specta globals **/
 